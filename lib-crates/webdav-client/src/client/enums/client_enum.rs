@@ -1,3 +1,4 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Depth {
     /// 仅返回当前资源
     Zero,
@@ -1,4 +1,4 @@
-use crate::client::error::WebDavClientError;
+use crate::client::error::{IntegrityCheck, WebDavClientError};
 use std::fmt::{Display, Formatter};
 
 impl Display for WebDavClientError {
@@ -15,6 +15,29 @@ impl Display for WebDavClientError {
             WebDavClientError::NotFindClient(e) => {
                 write!(f, "Not find Client from {}", e)
             }
+            WebDavClientError::AuthErr(e) => write!(f, "认证失败: {}", e),
+            WebDavClientError::Cancelled => write!(f, "传输已取消"),
+            WebDavClientError::IntegrityMismatch {
+                url,
+                check,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{check}校验失败，期望 {expected}，实际 {actual}: {url}"
+            ),
+        }
+    }
+}
+
+impl Display for IntegrityCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityCheck::ContentRange => write!(f, "分片范围"),
+            IntegrityCheck::Size => write!(f, "文件大小"),
+            IntegrityCheck::Checksum(algorithm) => {
+                write!(f, "{algorithm} 校验和")
+            }
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::client::structs::checksum::ChecksumAlgorithm;
 use tokio::sync::TryLockError;
 mod impl_display;
 mod impl_from;
@@ -12,5 +13,27 @@ pub enum WebDavClientError {
     SerdeErr(String),
     ParseUrlErr(String),
     TryLockError(TryLockError),
-    NotFindClient(String)
+    NotFindClient(String),
+    /// 认证失败（例如 OAuth2 刷新令牌失败）
+    AuthErr(String),
+    /// 传输被 `TransferControl::cancel` 取消
+    Cancelled,
+    /// 下载内容校验失败，`expected`/`actual` 是可读的期望值和实际值
+    IntegrityMismatch {
+        url: String,
+        check: IntegrityCheck,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// 完整性校验的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityCheck {
+    /// 分片响应的状态码或 `Content-Range` 和请求的范围不符
+    ContentRange,
+    /// 收到的字节数或最终文件大小和远端不一致
+    Size,
+    /// 文件哈希和服务端给出的校验和不一致
+    Checksum(ChecksumAlgorithm),
 }
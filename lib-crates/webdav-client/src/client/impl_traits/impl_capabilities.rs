@@ -0,0 +1,196 @@
+use crate::client::WebDavClient;
+use crate::client::enums::client_enum::Depth;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::propfind_with_client;
+use crate::client::structs::capabilities::{
+    ServerCapabilities, TCapabilitiesCache,
+};
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::propfind::PropfindRequest;
+use crate::client::structs::raw_xml::{MultiStatus, Prop};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::capabilities::Capabilities;
+use async_trait::async_trait;
+use reqwest::header::RANGE;
+use reqwest::{Method, StatusCode, Url};
+
+/// 列出根目录的直接子项，只取类型、大小和配额属性
+async fn propfind_root(
+    http_client: &WebDavHttpClient,
+    base_url: &str,
+) -> Result<MultiStatus, WebDavClientError> {
+    let propfind_request = PropfindRequest::prop()
+        .with_dav_prop("resourcetype")
+        .with_dav_prop("getcontentlength")
+        .with_quota_props();
+
+    propfind_with_client(
+        http_client,
+        base_url,
+        &Depth::One,
+        &propfind_request,
+    )
+    .await
+}
+
+/// 根目录那一条是否在 2xx 的 propstat 里返回了配额属性
+fn has_quota(multi_status: &MultiStatus, base_url: &Url) -> bool {
+    multi_status
+        .responses
+        .iter()
+        .filter(|response| {
+            base_url.join(&response.href).is_ok_and(|url| {
+                url.path().trim_end_matches('/')
+                    == base_url.path().trim_end_matches('/')
+            })
+        })
+        .flat_map(|response| &response.propstats)
+        .filter(|propstat| propstat.is_success())
+        .any(|propstat| {
+            let Prop { quota_available_bytes, quota_used_bytes, .. } =
+                &propstat.prop;
+            quota_available_bytes.is_some() || quota_used_bytes.is_some()
+        })
+}
+
+/// 用根目录下最小的文件试一次 `Range: bytes=0-0`
+/// - 206 说明支持，200 说明服务端忽略了 Range
+/// - 只读响应头，不读响应体
+async fn probe_ranges(
+    http_client: &WebDavHttpClient,
+    base_url: &Url,
+    multi_status: MultiStatus,
+) -> Option<bool> {
+    let smallest = FriendlyResource::new(multi_status)
+        .ok()?
+        .into_iter()
+        .filter(|resource| !resource.is_dir)
+        .filter(|resource| resource.size.is_some_and(|size| size > 0))
+        .min_by_key(|resource| resource.size)?;
+
+    let file_url = base_url.join(&smallest.full_path).ok()?;
+    let request =
+        http_client.get(file_url.as_str()).header(RANGE, "bytes=0-0");
+    let response = http_client.send(request).await.ok()?;
+
+    match response.status() {
+        StatusCode::PARTIAL_CONTENT => Some(true),
+        StatusCode::OK => Some(false),
+        _ => None,
+    }
+}
+
+/// 探测服务端能力
+/// - `OPTIONS` 必须成功，拿到合规等级、允许的方法和 DASL
+/// - 之后的 PROPFIND 和 Range 探测失败只会让对应项保持未知
+/// - 服务商配置了 `disable_ranges` 时不发 Range 探测请求
+pub async fn probe_capabilities_with_client(
+    http_client: &WebDavHttpClient,
+    base_url: &str,
+) -> Result<ServerCapabilities, WebDavClientError> {
+    let response = http_client
+        .send(http_client.request(Method::OPTIONS, base_url))
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(WebDavClientError::String(format!(
+            "探测服务端能力失败 {status}: {base_url}"
+        )));
+    }
+
+    let mut capabilities =
+        ServerCapabilities::from_options_headers(response.headers());
+
+    let base = Url::parse(base_url)
+        .map_err(|e| WebDavClientError::ParseUrlErr(e.to_string()))?;
+
+    if let Ok(multi_status) = propfind_root(http_client, base_url).await {
+        capabilities.supports_quota = has_quota(&multi_status, &base);
+
+        // 服务商禁用了分片时不探测，有的服务端把 1 字节的 Range 请求按整个文件计流量
+        let disable_ranges =
+            http_client.profile().is_some_and(|p| p.disable_ranges);
+        if capabilities.accept_ranges.is_none() && !disable_ranges {
+            capabilities.accept_ranges =
+                probe_ranges(http_client, &base, multi_status).await;
+        }
+    }
+
+    Ok(capabilities)
+}
+
+/// 读取账号上缓存的能力，没有缓存或 `refresh` 时重新探测
+/// - 探测失败不写缓存，下次调用再试
+pub async fn load_capabilities(
+    http_client: &WebDavHttpClient,
+    base_url: &str,
+    cache: &TCapabilitiesCache,
+    refresh: bool,
+) -> Result<ServerCapabilities, WebDavClientError> {
+    let mut cached = cache.lock().await;
+
+    if !refresh && let Some(capabilities) = cached.as_ref() {
+        return Ok(capabilities.clone());
+    }
+
+    let capabilities =
+        probe_capabilities_with_client(http_client, base_url).await?;
+    *cached = Some(capabilities.clone());
+    Ok(capabilities)
+}
+
+impl WebDavClient {
+    async fn capabilities_of(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        refresh: bool,
+    ) -> Result<ServerCapabilities, WebDavClientError> {
+        let value = self.try_get_client_arc(web_dav_child_client_key)?;
+        let guard = value.read().await;
+        let http_client = guard.client.clone();
+        let cache = guard.capabilities.clone();
+        drop(guard);
+
+        load_capabilities(
+            &http_client,
+            &web_dav_child_client_key.get_base_url(),
+            &cache,
+            refresh,
+        )
+        .await
+    }
+
+    /// 记下服务端是否接受 `Depth: infinity`，还没有探测结果时不记
+    pub(crate) async fn record_depth_infinity(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        supported: bool,
+    ) -> Result<(), WebDavClientError> {
+        let value = self.try_get_client_arc(web_dav_child_client_key)?;
+        let cache = value.read().await.capabilities.clone();
+
+        if let Some(capabilities) = cache.lock().await.as_mut() {
+            capabilities.depth_infinity = Some(supported);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Capabilities for WebDavClient {
+    async fn get_capabilities(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<ServerCapabilities, WebDavClientError> {
+        self.capabilities_of(web_dav_child_client_key, false).await
+    }
+
+    async fn refresh_capabilities(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<ServerCapabilities, WebDavClientError> {
+        self.capabilities_of(web_dav_child_client_key, true).await
+    }
+}
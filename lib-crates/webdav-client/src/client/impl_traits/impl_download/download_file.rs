@@ -1,101 +1,671 @@
-use crate::client::enums::client_enum::Depth;
 use crate::client::error::WebDavClientError;
-use crate::client::impl_traits::impl_folder::get_folders_with_client;
+use crate::client::impl_traits::impl_download::part_file::PartFile;
+use crate::client::impl_traits::impl_download::verify::{
+    check_content_range, check_received, response_checksums,
+};
+use crate::client::structs::checksum::Checksum;
 use crate::client::structs::friendly_xml::FriendlyResource;
-use futures_util::FutureExt;
-use futures_util::future::BoxFuture;
-use reqwest::Client;
-use reqwest::header::RANGE;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::progress::{FileProgress, Progress};
+use crate::client::structs::remote_tree::RemoteTree;
+use crate::client::structs::transfer_control::TransferControl;
+use crate::client::structs::transfer_pool::AccountPool;
+use chrono::{DateTime, FixedOffset};
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, StreamExt};
+use reqwest::header::{IF_RANGE, RANGE};
+use reqwest::{Response, StatusCode};
 use std::cmp::min;
 use std::path::Path;
+use std::time::SystemTime;
 use tokio::fs::{self, OpenOptions};
-use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
 
-const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+/// 不分片下载时，每写入这么多字节更新一次续传记录
+const COMMIT_INTERVAL: u64 = 4 * 1024 * 1024;
 
-/// === 工具函数：列出目录下的子资源 ===
-async fn list_directory(
-    http_client: &Client,
-    dir_url: &str,
-) -> Result<Vec<FriendlyResource>, WebDavClientError> {
-    // 发 PROPFIND 获取子文件列表
+/// 单个文件的下载参数，由 [`DownloadConfig`](crate::client::traits::download::DownloadConfig) 整理而来
+#[derive(Clone, Copy, Debug)]
+pub struct FileOptions {
+    /// 是否按 Range 分片下载
+    pub segmented: bool,
+    /// 分片大小（字节）
+    pub segment_size: u64,
+    /// 单个文件同时请求的分片数，多出来的分片还要占用传输池的空闲许可
+    pub concurrency: usize,
+    /// 目录里同时下载的文件数，同时还受传输池的上限约束
+    pub tree_concurrency: usize,
+    /// 本地已有完整文件时跳过
+    pub skip_existing: bool,
+    /// 下载完成后按服务端的校验和校验文件
+    pub verify_checksum: bool,
+    /// 下载完成后把本地修改时间设为远端的 `getlastmodified`
+    pub preserve_mtime: bool,
+}
+
+/// 把本地文件的修改时间设为远端的修改时间
+async fn set_local_mtime(
+    path: &str,
+    last_modified: DateTime<FixedOffset>,
+) -> Result<(), WebDavClientError> {
+    let file = OpenOptions::new().write(true).open(path).await?;
+    file.into_std().await.set_modified(SystemTime::from(last_modified))?;
+    Ok(())
+}
+
+/// 发送 GET，`start > 0` 时带上 `Range`，续传时再带上 `If-Range`，不检查状态码
+async fn request_get(
+    http_client: &WebDavHttpClient,
+    file_url: &str,
+    start: u64,
+    end: Option<u64>,
+    if_range: Option<&str>,
+) -> Result<Response, WebDavClientError> {
+    let mut request = http_client.get(file_url);
+
+    if start > 0 || end.is_some() {
+        let range_header = match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+        request = request.header(RANGE, range_header);
+    }
+    if let Some(if_range) = if_range {
+        request = request.header(IF_RANGE, if_range);
+    }
+
+    http_client.send(request).await
+}
+
+/// 非 2xx 的响应转成错误
+fn check_status(
+    resp: Response,
+    file_url: &str,
+) -> Result<Response, WebDavClientError> {
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(WebDavClientError::String(format!(
+            "下载失败 {status}: {file_url}"
+        )));
+    }
+
+    Ok(resp)
+}
+
+/// 发送 GET 并要求服务端返回 2xx
+async fn send_get(
+    http_client: &WebDavHttpClient,
+    file_url: &str,
+    start: u64,
+    end: Option<u64>,
+    if_range: Option<&str>,
+) -> Result<Response, WebDavClientError> {
     let resp =
-        get_folders_with_client(http_client, dir_url, &Depth::One).await?;
+        request_get(http_client, file_url, start, end, if_range).await?;
+    check_status(resp, file_url)
+}
 
-    // 转换成 FriendlyResource 列表
-    let resources = FriendlyResource::new(resp)?;
-    Ok(resources)
+/// 把响应体边收边写进 `.part`（从 `start` 处开始），返回写入的字节数
+/// - 不会把整个响应体读进内存，内存占用只和写盘缓冲区有关
+/// - 每写入 [`COMMIT_INTERVAL`] 字节更新一次续传记录，中途断开也能从最近的位置继续
+/// - 每收到一块数据检查一次暂停/取消，取消时先提交已写入的部分再返回
+async fn write_body(
+    resp: Response,
+    part_file: &mut PartFile,
+    start: u64,
+    file_progress: &FileProgress,
+    control: &TransferControl,
+) -> Result<u64, WebDavClientError> {
+    part_file.seek_to(start).await?;
+
+    let mut written: u64 = 0;
+    let mut uncommitted: u64 = 0;
+
+    let mut body = resp.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        if let Err(e) = control.checkpoint().await {
+            part_file.commit(start + written).await?;
+            return Err(e);
+        }
+
+        let chunk = chunk?;
+        part_file.write(&chunk).await?;
+        file_progress.add(chunk.len() as u64);
+
+        written += chunk.len() as u64;
+        uncommitted += chunk.len() as u64;
+
+        if uncommitted >= COMMIT_INTERVAL {
+            part_file.commit(start + written).await?;
+            uncommitted = 0;
+        }
+    }
+
+    part_file.commit(start + written).await?;
+    Ok(written)
 }
 
-pub fn download_file<'a>(
-    http_client: &'a Client,
-    resource: &'a FriendlyResource,
-    output_path: &'a str,
-    auto_segment_file: bool,
-) -> BoxFuture<'a, Result<(), WebDavClientError>> {
-    async move {
-        if resource.is_dir {
-            let dir_path = format!("{}/{}", output_path, resource.name);
-            fs::create_dir_all(&dir_path).await?;
-
-            let children =
-                list_directory(http_client, &resource.full_path).await?;
-            for child in children {
-                if child.full_path == resource.full_path {
-                    continue;
-                }
-                download_file(
-                    http_client,
-                    &child,
-                    &dir_path,
-                    auto_segment_file,
-                )
+/// 不分片：一次请求拿完剩下的部分
+/// - 有未完成的 `.part` 时请求 `bytes=已下载-`，服务端返回 200 说明远端已变或不支持 Range，从头写
+/// - 续传记录已经是完整大小（上次写完后、改名前退出）时不再请求，直接交给 `finish` 校验
+/// - 续传请求返回 416 说明记录和远端对不上，丢掉 `.part` 从头下载
+async fn download_whole(
+    http_client: &WebDavHttpClient,
+    resource: &FriendlyResource,
+    part_file: &mut PartFile,
+    file_progress: &FileProgress,
+    control: &TransferControl,
+) -> Result<(), WebDavClientError> {
+    let mut start = part_file.downloaded();
+    if start > 0 && resource.size == Some(start) {
+        return Ok(());
+    }
+
+    let mut resp = request_get(
+        http_client,
+        &resource.full_path,
+        start,
+        None,
+        part_file.if_range(),
+    )
+    .await?;
+
+    if start > 0 && resp.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        part_file.reset().await?;
+        file_progress.reset();
+        start = 0;
+        resp =
+            request_get(http_client, &resource.full_path, 0, None, None)
                 .await?;
+    }
+    let resp = check_status(resp, &resource.full_path)?;
+
+    part_file.add_checksums(response_checksums(&resp));
+
+    // 续传时按 206 的 Content-Range 确认从哪里接着写
+    let expected = if resp.status() == StatusCode::PARTIAL_CONTENT {
+        let end = check_content_range(
+            &resp,
+            &resource.full_path,
+            start,
+            None,
+            resource.size,
+        )?;
+        Some(end - start + 1)
+    } else {
+        if start > 0 {
+            part_file.reset().await?;
+            file_progress.reset();
+            start = 0;
+        }
+        resp.content_length()
+    };
+
+    let written =
+        write_body(resp, part_file, start, file_progress, control).await?;
+    if let Some(expected) = expected {
+        check_received(&resource.full_path, expected, written)?;
+    }
+    Ok(())
+}
+
+/// 分片：从 `.part` 已下载的位置开始逐片请求，每片写完就更新续传记录
+async fn download_segmented(
+    http_client: &WebDavHttpClient,
+    resource: &FriendlyResource,
+    part_file: &mut PartFile,
+    segment_size: u64,
+    file_progress: &FileProgress,
+    control: &TransferControl,
+) -> Result<(), WebDavClientError> {
+    let total_size = resource.size.unwrap_or(0);
+
+    let mut start = part_file.downloaded();
+    while start < total_size {
+        control.checkpoint().await?;
+        let end = min(start + segment_size - 1, total_size - 1);
+
+        let resp = send_get(
+            http_client,
+            &resource.full_path,
+            start,
+            Some(end),
+            part_file.if_range(),
+        )
+        .await?;
+
+        part_file.add_checksums(response_checksums(&resp));
+
+        // 200 说明服务端忽略了 Range（或 If-Range 不匹配），返回的是完整文件
+        if resp.status() != StatusCode::PARTIAL_CONTENT {
+            part_file.reset().await?;
+            file_progress.reset();
+            let expected = resp.content_length();
+            let written =
+                write_body(resp, part_file, 0, file_progress, control)
+                    .await?;
+            if let Some(expected) = expected {
+                check_received(&resource.full_path, expected, written)?;
             }
             return Ok(());
         }
 
-        let file_url = &resource.full_path;
-        let file_name = &resource.name;
-        let output_file_path = format!("{}/{}", output_path, file_name);
-        let total_size = resource.size.unwrap_or(0);
+        // 服务端可以只返回请求范围的前一部分，按 Content-Range 给出的终点推进
+        let end = check_content_range(
+            &resp,
+            &resource.full_path,
+            start,
+            Some(end),
+            resource.size,
+        )?;
+        let written =
+            write_body(resp, part_file, start, file_progress, control)
+                .await?;
+        check_received(&resource.full_path, end - start + 1, written)?;
+        start += written;
+    }
+
+    Ok(())
+}
+
+/// 并发分片共用的目标信息
+struct SegmentTarget<'a> {
+    file_url: &'a str,
+    part_path: &'a Path,
+    validator: Option<&'a str>,
+    total_size: u64,
+}
+
+/// 下载单个分片并写到 `.part` 的对应位置（每个分片各自打开一个文件句柄）
+/// - 返回分片序号和响应头里的校验和
+/// - 返回 `None` 表示服务端没有按 Range 返回（200），调用方需要放弃并发分片
+/// - `Content-Range` 或写入字节数和分片不一致时视为失败，避免留下空洞
+async fn download_segment(
+    http_client: &WebDavHttpClient,
+    target: &SegmentTarget<'_>,
+    index: usize,
+    (start, end): (u64, u64),
+    file_progress: &FileProgress,
+    control: &TransferControl,
+) -> Result<Option<(usize, Vec<Checksum>)>, WebDavClientError> {
+    let SegmentTarget { file_url, part_path, validator, total_size } =
+        *target;
+
+    control.checkpoint().await?;
+
+    let resp =
+        send_get(http_client, file_url, start, Some(end), validator)
+            .await?;
+
+    if resp.status() != StatusCode::PARTIAL_CONTENT {
+        return Ok(None);
+    }
+
+    // 分片之间按位置拼接，服务端少给一点也会留下空洞，所以要求范围完全一致
+    let range_end = check_content_range(
+        &resp,
+        file_url,
+        start,
+        Some(end),
+        Some(total_size),
+    )?;
+    check_received(file_url, end - start + 1, range_end - start + 1)?;
+    let checksums = response_checksums(&resp);
+
+    let file = OpenOptions::new().write(true).open(part_path).await?;
+    let mut writer = BufWriter::new(file);
+    writer.seek(std::io::SeekFrom::Start(start)).await?;
 
-        if let Some(parent) = Path::new(&output_file_path).parent() {
-            fs::create_dir_all(parent).await?;
+    let mut written: u64 = 0;
+    let mut body = resp.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        control.checkpoint().await?;
+
+        let chunk = chunk?;
+        writer.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+        file_progress.add(chunk.len() as u64);
+    }
+    writer.flush().await?;
+
+    check_received(file_url, end - start + 1, written)?;
+
+    Ok(Some((index, checksums)))
+}
+
+/// 并发分片：预分配文件后同时请求 `concurrency` 个分片，各自写到对应位置
+/// - 文件本身占着一个传输池许可，第一个分片之外的每个在途分片再各占一个
+/// - 传输池没有空闲许可时少开分片，等在途的分片结束后再试，不会等待许可
+/// - 续传记录只记录"从头开始连续完成"的部分，中断后最多重下 `concurrency - 1` 个分片
+/// - 任意分片收到 200 时，说明服务端不支持 Range，退回不分片下载
+async fn download_parallel(
+    http_client: &WebDavHttpClient,
+    resource: &FriendlyResource,
+    part_file: &mut PartFile,
+    file_options: FileOptions,
+    file_progress: &FileProgress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> Result<(), WebDavClientError> {
+    let FileOptions { segment_size, concurrency, .. } = file_options;
+    let total_size = resource.size.unwrap_or(0);
+    let start = part_file.downloaded();
+
+    let segments: Vec<(u64, u64)> = (start..total_size)
+        .step_by(segment_size as usize)
+        .map(|s| (s, min(s + segment_size - 1, total_size - 1)))
+        .collect();
+
+    part_file.preallocate(total_size).await?;
+
+    let part_path = part_file.part_path().to_path_buf();
+    let validator = part_file.validator().map(str::to_string);
+    let target = SegmentTarget {
+        file_url: &resource.full_path,
+        part_path: &part_path,
+        validator: validator.as_deref(),
+        total_size,
+    };
+
+    let mut done = vec![false; segments.len()];
+    let mut done_prefix = 0;
+    let mut next = 0;
+    let mut in_flight = FuturesUnordered::new();
+
+    loop {
+        while in_flight.len() < concurrency && next < segments.len() {
+            let permit = if in_flight.is_empty() {
+                None
+            } else {
+                match account_pool.try_acquire() {
+                    Some(permit) => Some(permit),
+                    None => break,
+                }
+            };
+
+            in_flight.push(
+                download_segment(
+                    http_client,
+                    &target,
+                    next,
+                    segments[next],
+                    file_progress,
+                    control,
+                )
+                .map(move |result| {
+                    drop(permit);
+                    result
+                }),
+            );
+            next += 1;
         }
 
-        if total_size == 0 || !auto_segment_file {
-            let resp = http_client.get(file_url).send().await?;
-            let bytes = resp.bytes().await?;
-            tokio::fs::write(&output_file_path, &bytes).await?;
-            return Ok(());
+        let index = match in_flight.next().await {
+            Some(result) => result?,
+            None => break,
+        };
+
+        let Some((index, checksums)) = index else {
+            drop(in_flight);
+            part_file.reset().await?;
+            file_progress.reset();
+            return download_whole(
+                http_client,
+                resource,
+                part_file,
+                file_progress,
+                control,
+            )
+            .await;
+        };
+
+        part_file.add_checksums(checksums);
+        done[index] = true;
+        while done_prefix < segments.len() && done[done_prefix] {
+            done_prefix += 1;
+        }
+
+        let downloaded = match segments.get(done_prefix) {
+            Some((segment_start, _)) => *segment_start,
+            None => total_size,
+        };
+        part_file.commit(downloaded).await?;
+    }
+
+    Ok(())
+}
+
+/// 下载单个文件到 `output_file_path`，按分片参数选择下载方式
+/// - 返回最终文件大小，跳过时返回 `None`
+/// - 被取消时按 `control` 的设置保留或删除 `.part`
+/// - `account_pool` 用来给并发分片申请额外的许可
+async fn download_one(
+    http_client: &WebDavHttpClient,
+    resource: &FriendlyResource,
+    output_file_path: &str,
+    file_options: FileOptions,
+    progress: &Progress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> Result<Option<u64>, WebDavClientError> {
+    control.checkpoint().await?;
+
+    if file_options.skip_existing
+        && PartFile::is_complete(output_file_path, resource.size).await
+    {
+        progress.skip_file(
+            &resource.full_path,
+            output_file_path,
+            resource.size,
+        );
+        return Ok(None);
+    }
+
+    if let Some(parent) = Path::new(output_file_path).parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let mut part_file = match PartFile::open(output_file_path, resource)
+        .await
+    {
+        Ok(part_file) => part_file,
+        Err(e) => {
+            progress.fail_file(&resource.full_path, output_file_path, &e);
+            return Err(e);
+        }
+    };
+
+    let file_progress = progress.start_file(
+        &resource.full_path,
+        output_file_path,
+        resource.size,
+        part_file.downloaded(),
+    );
+
+    let FileOptions { segmented, segment_size, concurrency, .. } =
+        file_options;
+
+    let transferred = if resource.size.unwrap_or(0) == 0
+        || !segmented
+        || segment_size == 0
+    {
+        download_whole(
+            http_client,
+            resource,
+            &mut part_file,
+            &file_progress,
+            control,
+        )
+        .await
+    } else if concurrency > 1 {
+        download_parallel(
+            http_client,
+            resource,
+            &mut part_file,
+            file_options,
+            &file_progress,
+            control,
+            account_pool,
+        )
+        .await
+    } else {
+        download_segmented(
+            http_client,
+            resource,
+            &mut part_file,
+            segment_size,
+            &file_progress,
+            control,
+        )
+        .await
+    };
+
+    let result = match transferred {
+        Ok(()) => {
+            let finished = part_file
+                .finish(resource.size, file_options.verify_checksum)
+                .await;
+            match (finished, resource.last_modified) {
+                (Ok(size), Some(last_modified))
+                    if file_options.preserve_mtime =>
+                {
+                    set_local_mtime(output_file_path, last_modified)
+                        .await
+                        .map(|_| Some(size))
+                }
+                (finished, _) => finished.map(Some),
+            }
+        }
+        Err(WebDavClientError::Cancelled)
+            if !control.keeps_partial_on_cancel() =>
+        {
+            part_file.discard().await?;
+            Err(WebDavClientError::Cancelled)
         }
+        Err(e) => Err(e),
+    };
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .open(&output_file_path)
+    match &result {
+        Ok(_) => file_progress.finish(),
+        Err(e) => file_progress.fail(e),
+    }
+    result
+}
+
+/// 按遍历好的目录树下载到 `dir_path`
+/// - 先建好所有目录（包括空目录），再把每个文件交给传输池，和顶层文件共享全局/主机/账号上限
+/// - 最多 `tree_concurrency` 个文件在途；单个文件失败不影响其他文件，全部结束后再汇总
+/// - 返回落盘的字节数，被跳过的文件不计
+pub async fn download_tree(
+    http_client: &WebDavHttpClient,
+    tree: &RemoteTree,
+    dir_path: &str,
+    file_options: FileOptions,
+    progress: &Progress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> Result<u64, WebDavClientError> {
+    fs::create_dir_all(dir_path).await?;
+    for dir in tree.dirs() {
+        fs::create_dir_all(format!("{}/{}", dir_path, dir.relative_path))
             .await?;
+    }
 
-        let mut start: u64 = 0;
-        while start < total_size {
-            let end = min(start + CHUNK_SIZE - 1, total_size - 1);
-            let range_header = format!("bytes={}-{}", start, end);
+    let max_in_flight = file_options.tree_concurrency.max(1);
+    let mut files = tree.files();
+    let mut in_flight = FuturesUnordered::new();
 
-            let resp = http_client
-                .get(file_url)
-                .header(RANGE, range_header)
-                .send()
-                .await?;
+    let mut bytes = 0;
+    let mut failed = 0;
+    let mut first_error = None;
 
-            let chunk = resp.bytes().await?;
-            file.seek(std::io::SeekFrom::Start(start)).await?;
-            file.write_all(&chunk).await?;
-            start += CHUNK_SIZE;
+    loop {
+        while in_flight.len() < max_in_flight {
+            let Some(file) = files.next() else {
+                break;
+            };
+
+            let relative_path = file.relative_path.clone();
+            let task = {
+                let client = http_client.clone();
+                let resource = file.resource.clone();
+                let output_file_path =
+                    format!("{}/{}", dir_path, file.relative_path);
+                let progress = progress.clone();
+                let control = control.clone();
+                let account_pool = account_pool.clone();
+                async move {
+                    download_one(
+                        &client,
+                        &resource,
+                        &output_file_path,
+                        file_options,
+                        &progress,
+                        &control,
+                        &account_pool,
+                    )
+                    .await
+                }
+            };
+
+            let spawned = match control.checkpoint().await {
+                Ok(()) => account_pool.spawn(task).await,
+                Err(e) => Err(e),
+            };
+
+            match spawned {
+                Ok(handle) => in_flight.push(handle.map(move |result| {
+                    (relative_path, result.and_then(|written| written))
+                })),
+                Err(e) => {
+                    failed += 1;
+                    first_error.get_or_insert((relative_path, e));
+                }
+            }
+        }
+
+        match in_flight.next().await {
+            Some((_, Ok(written))) => bytes += written.unwrap_or(0),
+            Some((relative_path, Err(e))) => {
+                failed += 1;
+                first_error.get_or_insert((relative_path, e));
+            }
+            None => break,
         }
+    }
 
-        file.flush().await?;
-        Ok(())
+    match first_error {
+        None => Ok(bytes),
+        Some(_) if control.is_cancelled() => {
+            Err(WebDavClientError::Cancelled)
+        }
+        Some((relative_path, e)) => {
+            Err(WebDavClientError::String(format!(
+                "目录中 {failed} 个文件下载失败，其中 {relative_path}: {e}"
+            )))
+        }
     }
-    .boxed()
+}
+
+/// 下载单个文件到 `output_path` 下
+/// - 返回落盘的字节数，被跳过时返回 `None`
+pub async fn download_file(
+    http_client: &WebDavHttpClient,
+    resource: &FriendlyResource,
+    output_path: &str,
+    file_options: FileOptions,
+    progress: &Progress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> Result<Option<u64>, WebDavClientError> {
+    download_one(
+        http_client,
+        resource,
+        &format!("{}/{}", output_path, resource.name),
+        file_options,
+        progress,
+        control,
+        account_pool,
+    )
+    .await
 }
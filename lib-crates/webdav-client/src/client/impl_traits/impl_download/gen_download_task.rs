@@ -1,49 +1,129 @@
 use crate::client::error::WebDavClientError;
-use crate::client::impl_traits::impl_download::TSuccessMetas;
-use crate::client::impl_traits::impl_download::download_file::download_file;
-use crate::public_traits::friendly::FriendlyXml;
+use crate::client::impl_traits::impl_download::download_file::{
+    FileOptions, download_file, download_tree,
+};
+use crate::client::impl_traits::impl_download::{
+    DownloadTarget, TDownloadTargets,
+};
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::progress::Progress;
+use crate::client::structs::transfer_control::TransferControl;
+use crate::client::structs::transfer_pool::AccountPool;
+use crate::client::traits::download::{
+    DownloadFileReport, DownloadOutcome,
+};
 use futures_util::future::BoxFuture;
-use futures_util::stream::FuturesUnordered;
-use reqwest::Client;
+use std::time::{Duration, Instant};
 
-type TDownloadTask = BoxFuture<'static, Result<(), WebDavClientError>>;
+pub type TDownloadTask = BoxFuture<'static, DownloadFileReport>;
 
-/// 生成下载任务，但不执行
-pub fn gen_download_tasks(
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
+/// 把一个请求路径的下载结果整理成报告
+pub fn target_report(
+    target: &DownloadTarget,
     output_path: &str,
-    auto_segment_file: bool,
-) -> FuturesUnordered<TDownloadTask> {
-    let download_tasks: FuturesUnordered<TDownloadTask> =
-        FuturesUnordered::new();
+    elapsed: Duration,
+    result: Result<Option<u64>, WebDavClientError>,
+) -> DownloadFileReport {
+    let (bytes, outcome) = match result {
+        Ok(Some(bytes)) => (bytes, DownloadOutcome::Downloaded),
+        Ok(None) => (0, DownloadOutcome::Skipped),
+        Err(e) => (0, DownloadOutcome::Failed(e)),
+    };
+
+    DownloadFileReport {
+        path: target.path.clone(),
+        local_path: Some(target.local_path(output_path)),
+        is_dir: target.resource.is_dir,
+        bytes,
+        elapsed,
+        outcome,
+    }
+}
+
+/// 下载一个请求路径（文件或目录），并记录耗时
+/// - 目录需要先遍历成 `tree`，里面的文件各自向 `account_pool` 申请许可
+/// - 单个文件并发分片时，多出来的分片也向 `account_pool` 申请许可
+pub async fn download_target(
+    http_client: &WebDavHttpClient,
+    target: &DownloadTarget,
+    output_path: &str,
+    file_options: FileOptions,
+    progress: &Progress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> DownloadFileReport {
+    let start = Instant::now();
+
+    let result = match (target.resource.is_dir, &target.tree) {
+        (true, Some(tree)) => download_tree(
+            http_client,
+            tree,
+            &target.local_path(output_path),
+            file_options,
+            progress,
+            control,
+            account_pool,
+        )
+        .await
+        .map(Some),
+        (true, None) => Err(WebDavClientError::String(format!(
+            "目录没有遍历结果: {}",
+            target.resource.full_path
+        ))),
+        (false, _) => {
+            download_file(
+                http_client,
+                &target.resource,
+                output_path,
+                file_options,
+                progress,
+                control,
+                account_pool,
+            )
+            .await
+        }
+    };
+
+    target_report(target, output_path, start.elapsed(), result)
+}
 
+/// 生成下载任务，但不执行，和 `targets` 一一对应
+/// - 任务都是 `'static` 的，可以直接交给传输池 spawn
+pub fn gen_download_tasks(
+    http_client: &WebDavHttpClient,
+    targets: &TDownloadTargets,
+    output_path: &str,
+    file_options: FileOptions,
+    progress: &Progress,
+    control: &TransferControl,
+    account_pool: &AccountPool,
+) -> Vec<TDownloadTask> {
     // 这里就把 output_path 转成 String，move 进去
     let output_path = output_path.to_string();
 
-    for file_meta in file_metas {
-        if let Ok(friendly_webdav_files_xml) = file_meta.to_friendly() {
-            if let Some(friendly_resource) =
-                friendly_webdav_files_xml.first()
-            {
-                let resource = friendly_resource.clone();
-                let client = http_client.clone();
-                let output_path = output_path.clone();
-
-                let fut: TDownloadTask = Box::pin(async move {
-                    download_file(
-                        &client,
-                        &resource,
-                        &output_path,
-                        auto_segment_file,
-                    )
-                    .await
-                });
-
-                download_tasks.push(fut);
-            }
-        }
-    }
+    targets
+        .iter()
+        .map(|target| {
+            let target = target.clone();
+            let client = http_client.clone();
+            let output_path = output_path.clone();
+            let progress = progress.clone();
+            let control = control.clone();
+            let account_pool = account_pool.clone();
 
-    download_tasks
+            let fut: TDownloadTask = Box::pin(async move {
+                download_target(
+                    &client,
+                    &target,
+                    &output_path,
+                    file_options,
+                    &progress,
+                    &control,
+                    &account_pool,
+                )
+                .await
+            });
+            fut
+        })
+        .collect()
 }
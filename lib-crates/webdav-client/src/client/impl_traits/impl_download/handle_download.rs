@@ -1,157 +1,254 @@
-use futures_util::StreamExt;
-use reqwest::Client;
-use crate::client::error::WebDavClientError;
-use crate::client::impl_traits::impl_download::download_file::download_file;
-use crate::client::impl_traits::impl_download::gen_download_task::gen_download_tasks;
-use crate::client::impl_traits::impl_download::TSuccessMetas;
-use crate::client::impl_traits::impl_download::chunked_download_blacklist::is_chunked_download_blacklisted;
+use crate::client::impl_traits::impl_download::download_file::FileOptions;
+use crate::client::impl_traits::impl_download::gen_download_task::{
+    download_target, gen_download_tasks, target_report,
+};
+use crate::client::impl_traits::impl_download::{
+    TDownloadTargets, TIndexedReports,
+};
+use crate::client::structs::capabilities::ServerCapabilities;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::progress::Progress;
+use crate::client::structs::transfer_control::TransferControl;
+use crate::client::structs::transfer_pool::{AccountPool, TransferPool};
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
 use crate::client::traits::download::{DownloadConfig, ThreadMode};
-use crate::public_traits::friendly::FriendlyXml;
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, StreamExt};
+use std::time::Duration;
+
+/// 一次下载调用里所有文件共用的参数
+struct DownloadContext<'a> {
+    account_pool: AccountPool,
+    http_client: &'a WebDavHttpClient,
+    output_path: &'a str,
+    file_options: FileOptions,
+    progress: Progress,
+    control: TransferControl,
+}
 
 /// 串行下载
+/// - 每个文件同样要先拿到传输池的许可，和其他调用共享上限
+/// - 目录不占许可，里面的文件逐个申请，否则上限为 1 时目录会等自己的文件
+/// - 暂停时在文件之间等待，取消后剩下的路径都记为取消
 async fn download_single_thread(
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
-    output_path: &str,
-    auto_segment_file: bool,
-) -> Result<(), WebDavClientError> {
-    for file_meta in file_metas {
-        if let Ok(friendly_webdav_files_xml) = file_meta.to_friendly() {
-            if let Some(resource) = friendly_webdav_files_xml.first() {
-                download_file(
-                    http_client,
-                    resource,
-                    output_path,
-                    auto_segment_file,
+    context: &DownloadContext<'_>,
+    targets: &TDownloadTargets,
+) -> TIndexedReports {
+    let mut reports = Vec::with_capacity(targets.len());
+
+    for target in targets {
+        let permit = match context.control.checkpoint().await {
+            Ok(()) if target.resource.is_dir => Ok(None),
+            Ok(()) => context.account_pool.acquire().await.map(Some),
+            Err(e) => Err(e),
+        };
+
+        let report = match permit {
+            Ok(_permit) => {
+                download_target(
+                    context.http_client,
+                    target,
+                    context.output_path,
+                    context.file_options,
+                    &context.progress,
+                    &context.control,
+                    &context.account_pool,
                 )
-                .await?;
+                .await
             }
-        }
+            Err(e) => target_report(
+                target,
+                context.output_path,
+                Duration::ZERO,
+                Err(e),
+            ),
+        };
+
+        reports.push((target.index, report));
     }
-    Ok(())
+
+    reports
 }
 
 /// 并行下载
+/// - 每个文件是传输池 spawn 出来的独立任务，真正跑在多个工作线程上
+/// - 目录不占许可，只负责把里面的文件逐个交给传输池
+/// - 本次调用最多 `max_concurrency` 个文件在途，拿不到传输池许可时等待，不会一次性发出全部请求
+/// - 单个路径失败不影响其他路径，每个路径都有一条报告
+/// - 暂停时不再派发新文件，取消后没派发的路径都记为取消
 async fn download_multi_thread(
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
-    output_path: &str,
-    auto_segment_file: bool,
-) -> Result<(), WebDavClientError> {
-    let mut tasks = gen_download_tasks(
-        http_client,
-        file_metas,
-        output_path,
-        auto_segment_file,
+    context: &DownloadContext<'_>,
+    targets: &TDownloadTargets,
+    max_concurrency: usize,
+) -> TIndexedReports {
+    let tasks = gen_download_tasks(
+        context.http_client,
+        targets,
+        context.output_path,
+        context.file_options,
+        &context.progress,
+        &context.control,
+        &context.account_pool,
     );
+    let mut tasks = targets.iter().zip(tasks);
 
-    let mut errors = Vec::new();
+    let max_concurrency = max_concurrency.max(1);
+    let mut in_flight = FuturesUnordered::new();
+    let mut reports = Vec::with_capacity(targets.len());
 
-    while let Some(result) = tasks.next().await {
-        if let Err(e) = result {
-            errors.push(e);
+    loop {
+        while in_flight.len() < max_concurrency {
+            let Some((target, task)) = tasks.next() else {
+                break;
+            };
+
+            let spawned = match context.control.checkpoint().await {
+                Ok(()) if target.resource.is_dir => {
+                    Ok(task.map(Ok).boxed())
+                }
+                Ok(()) => context
+                    .account_pool
+                    .spawn(task)
+                    .await
+                    .map(FutureExt::boxed),
+                Err(e) => Err(e),
+            };
+
+            match spawned {
+                Ok(handle) => in_flight.push(async move {
+                    let report = handle.await.unwrap_or_else(|e| {
+                        target_report(
+                            target,
+                            context.output_path,
+                            Duration::ZERO,
+                            Err(e),
+                        )
+                    });
+                    (target.index, report)
+                }),
+                Err(e) => reports.push((
+                    target.index,
+                    target_report(
+                        target,
+                        context.output_path,
+                        Duration::ZERO,
+                        Err(e),
+                    ),
+                )),
+            }
         }
-    }
 
-    if !errors.is_empty() {
-        return Err(errors.into_iter().next().unwrap());
+        match in_flight.next().await {
+            Some(report) => reports.push(report),
+            None => break,
+        }
     }
 
-    Ok(())
+    reports
 }
 
-/// 自动选择模式
-async fn download_auto(
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
+pub async fn handle_download(
+    transfer_pool: &TransferPool,
+    web_dav_child_client_key: &WebDavChildClientKey,
+    http_client: &WebDavHttpClient,
+    targets: &TDownloadTargets,
     output_path: &str,
-    auto_segment_file: bool,
-) -> Result<(), WebDavClientError> {
-    if file_metas.len() > 1 {
+    download_config: &DownloadConfig,
+    file_options: FileOptions,
+) -> TIndexedReports {
+    let progress = Progress::new(download_config.progress.clone());
+
+    // 目录在开始前已经遍历完，传输前就能登记全部文件
+    progress.add_files(targets.iter().flat_map(|target| {
+        let top =
+            (!target.resource.is_dir).then_some(target.resource.size);
+        let tree_files = target
+            .tree
+            .iter()
+            .flat_map(|tree| tree.files())
+            .map(|entry| entry.resource.size);
+        top.into_iter().chain(tree_files)
+    }));
+
+    let context = DownloadContext {
+        account_pool: transfer_pool.for_account(web_dav_child_client_key),
+        http_client,
+        output_path,
+        file_options,
+        progress,
+        control: download_config.control.clone().unwrap_or_default(),
+    };
+
+    // 自动模式：多个文件才走并行
+    let multi_thread = match download_config.thread_mode {
+        ThreadMode::SingleThread => false,
+        ThreadMode::MultipleThread => true,
+        ThreadMode::Auto => targets.len() > 1,
+    };
+
+    if multi_thread {
         download_multi_thread(
-            http_client,
-            file_metas,
-            output_path,
-            auto_segment_file,
+            &context,
+            targets,
+            download_config.max_concurrency,
         )
         .await
     } else {
-        download_single_thread(
-            http_client,
-            file_metas,
-            output_path,
-            auto_segment_file,
-        )
-        .await
-    }
-}
-
-pub async fn handle_download(
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
-    output_path: &str,
-    thread_mode: &ThreadMode,
-    auto_segment_file: bool,
-) -> Result<(), WebDavClientError> {
-    match thread_mode {
-        ThreadMode::SingleThread => {
-            download_single_thread(
-                http_client,
-                file_metas,
-                output_path,
-                auto_segment_file,
-            )
-            .await
-        }
-        ThreadMode::MultipleThread => {
-            download_multi_thread(
-                http_client,
-                file_metas,
-                output_path,
-                auto_segment_file,
-            )
-            .await
-        }
-        ThreadMode::Auto => {
-            download_auto(
-                http_client,
-                file_metas,
-                output_path,
-                auto_segment_file,
-            )
-            .await
-        }
+        download_single_thread(&context, targets).await
     }
 }
 
 /// 预处理下载
 pub async fn preprocessing_download(
+    transfer_pool: &TransferPool,
     web_dav_child_client_key: &WebDavChildClientKey,
     download_config: &DownloadConfig,
-    http_client: &Client,
-    file_metas: &TSuccessMetas,
+    capabilities: &ServerCapabilities,
+    http_client: &WebDavHttpClient,
+    targets: &TDownloadTargets,
     output_path: &str,
-) -> Result<(), WebDavClientError> {
-    let DownloadConfig { thread_mode, auto_segment_file } =
-        download_config;
+) -> TIndexedReports {
+    let DownloadConfig {
+        thread_mode,
+        auto_segment_file,
+        segment_size,
+        segment_concurrency,
+        max_concurrency,
+        skip_existing,
+        verify_checksum,
+        preserve_mtime,
+        ..
+    } = download_config;
 
-    let base_url = web_dav_child_client_key.get_base_url();
+    // 服务端不支持 Range 或服务商配置禁用了分片时不分片
+    let auto_segment_file = *auto_segment_file
+        && capabilities.supports_ranges()
+        && !http_client.profile().is_some_and(|p| p.disable_ranges);
 
-    let auto_segment_file = if is_chunked_download_blacklisted(&base_url) {
-        false
-    } else {
-        *auto_segment_file
+    // 单线程模式下单个文件也不并发请求分片，目录里的文件也逐个下载
+    let (concurrency, tree_concurrency) = match thread_mode {
+        ThreadMode::SingleThread => (1, 1),
+        _ => ((*segment_concurrency).max(1), (*max_concurrency).max(1)),
+    };
+
+    let file_options = FileOptions {
+        segmented: auto_segment_file,
+        segment_size: *segment_size,
+        concurrency,
+        tree_concurrency,
+        skip_existing: *skip_existing,
+        verify_checksum: *verify_checksum,
+        preserve_mtime: *preserve_mtime,
     };
 
     handle_download(
+        transfer_pool,
+        web_dav_child_client_key,
         http_client,
-        file_metas,
+        targets,
         output_path,
-        thread_mode,
-        auto_segment_file,
+        download_config,
+        file_options,
     )
-    .await?;
-
-    Ok(())
+    .await
 }
@@ -1,38 +1,157 @@
-mod chunked_download_blacklist;
 mod download_file;
 mod gen_download_task;
 mod handle_download;
+mod part_file;
+mod verify;
 
 use crate::client::WebDavClient;
 use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_download::gen_download_task::target_report;
 use crate::client::impl_traits::impl_download::handle_download::preprocessing_download;
-use crate::client::structs::raw_xml::MultiStatus;
+use crate::client::impl_traits::impl_url_parse::as_dir_path;
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::remote_tree::RemoteTree;
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
-use crate::client::traits::download::{Download, DownloadConfig};
+use crate::client::traits::capabilities::Capabilities;
+use crate::client::traits::download::{
+    Download, DownloadConfig, DownloadFileReport, DownloadOutcome,
+    DownloadReport,
+};
 use crate::client::traits::folder::{Folder, TFileMetas};
+use crate::client::traits::url_trait::UrlParse;
+use crate::public_traits::friendly::FriendlyXml;
 use async_trait::async_trait;
+use std::time::Duration;
 
-type TFailedMetasError = Vec<WebDavClientError>;
+/// 一个请求路径及其远端信息
+#[derive(Clone)]
+struct DownloadTarget {
+    /// 在 `files_path` 里的位置，报告按它排序
+    index: usize,
+    /// 调用时传入的远端路径
+    path: String,
+    resource: FriendlyResource,
+    /// 目录的完整文件列表，传输开始前遍历好
+    tree: Option<RemoteTree>,
+}
+
+impl DownloadTarget {
+    /// 下载到本地的路径
+    fn local_path(&self, output_path: &str) -> String {
+        format!("{}/{}", output_path, self.resource.name)
+    }
+}
+
+type TDownloadTargets = Vec<DownloadTarget>;
+type TIndexedReports = Vec<(usize, DownloadFileReport)>;
+
+/// 获取远端信息失败的路径，直接记为失败
+fn failed_meta_report(
+    path: &str,
+    error: WebDavClientError,
+) -> DownloadFileReport {
+    DownloadFileReport {
+        path: path.to_string(),
+        local_path: None,
+        is_dir: false,
+        bytes: 0,
+        elapsed: Duration::ZERO,
+        outcome: DownloadOutcome::Failed(error),
+    }
+}
 
-type TSuccessMetas = Vec<MultiStatus>;
-fn collect_file_metas_result(
+/// 把 PROPFIND 结果和请求路径对应起来（`collect_file_metas` 按请求顺序返回）
+/// - 失败的路径直接生成报告，不再丢弃
+fn collect_download_targets(
+    files_path: &[String],
     file_metas_result: TFileMetas,
-) -> (TSuccessMetas, TFailedMetasError) {
-    let mut failed_metas_error = Vec::new();
-    let mut success_metas = Vec::new();
-
-    for file_meta_result in file_metas_result {
-        match file_meta_result {
-            Ok(file_meta) => {
-                success_metas.push(file_meta);
-            }
+) -> (TDownloadTargets, TIndexedReports) {
+    let mut targets = Vec::new();
+    let mut failed_reports = Vec::new();
+
+    for (index, (path, file_meta_result)) in
+        files_path.iter().zip(file_metas_result).enumerate()
+    {
+        let resource = file_meta_result.and_then(|file_meta| {
+            file_meta.to_friendly()?.into_iter().next().ok_or_else(|| {
+                WebDavClientError::String(format!(
+                    "服务端没有返回资源信息: {path}"
+                ))
+            })
+        });
+
+        match resource {
+            Ok(resource) => targets.push(DownloadTarget {
+                index,
+                path: path.clone(),
+                resource,
+                tree: None,
+            }),
             Err(err) => {
-                failed_metas_error.push(err);
+                failed_reports.push((index, failed_meta_report(path, err)))
             }
         }
     }
 
-    (success_metas, failed_metas_error)
+    (targets, failed_reports)
+}
+
+impl WebDavClient {
+    /// 遍历所有目录，得到完整的文件列表
+    /// - 遍历失败的目录直接记为失败，不再下载
+    async fn walk_download_targets(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        targets: TDownloadTargets,
+        output_path: &str,
+        max_concurrency: usize,
+    ) -> (TDownloadTargets, TIndexedReports) {
+        let mut walked = Vec::with_capacity(targets.len());
+        let mut failed_reports = Vec::new();
+
+        for mut target in targets {
+            if !target.resource.is_dir {
+                walked.push(target);
+                continue;
+            }
+
+            let tree = match self
+                .format_url_path(
+                    web_dav_child_client_key,
+                    &as_dir_path(&target.path),
+                )
+                .await
+            {
+                Ok(dir_url) => {
+                    self.walk_tree_of(
+                        web_dav_child_client_key,
+                        &dir_url,
+                        max_concurrency,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+
+            match tree {
+                Ok(tree) => {
+                    target.tree = Some(tree);
+                    walked.push(target);
+                }
+                Err(e) => failed_reports.push((
+                    target.index,
+                    target_report(
+                        &target,
+                        output_path,
+                        Duration::ZERO,
+                        Err(e),
+                    ),
+                )),
+            }
+        }
+
+        (walked, failed_reports)
+    }
 }
 
 #[async_trait]
@@ -43,29 +162,53 @@ impl Download for WebDavClient {
         files_path: Vec<String>,
         output_path: &str,
         download_config: Option<DownloadConfig>,
-    ) -> Result<String, WebDavClientError> {
+    ) -> Result<DownloadReport, WebDavClientError> {
         let file_metas_result = self
             .collect_file_metas(web_dav_child_client_key, &files_path)
             .await?;
 
-        let (success_metas, _failed_metas_error) =
-            collect_file_metas_result(file_metas_result);
+        let (targets, mut reports) =
+            collect_download_targets(&files_path, file_metas_result);
 
         let download_config = download_config
             .unwrap_or(DownloadConfig::new_default_config());
 
+        let (targets, walk_failed_reports) = self
+            .walk_download_targets(
+                web_dav_child_client_key,
+                targets,
+                output_path,
+                download_config.max_concurrency,
+            )
+            .await;
+        reports.extend(walk_failed_reports);
+
         let http_client =
             self.try_get_client_entity(web_dav_child_client_key).await?;
 
-        preprocessing_download(
-            web_dav_child_client_key,
-            &download_config,
-            &http_client,
-            &success_metas,
-            &output_path,
-        )
-        .await?;
+        // 探测失败时按默认能力处理，分片下载遇到 200 会自动退回整文件
+        let capabilities = self
+            .get_capabilities(web_dav_child_client_key)
+            .await
+            .unwrap_or_default();
+
+        reports.extend(
+            preprocessing_download(
+                &self.transfer_pool,
+                web_dav_child_client_key,
+                &download_config,
+                &capabilities,
+                &http_client,
+                &targets,
+                output_path,
+            )
+            .await,
+        );
+
+        reports.sort_by_key(|(index, _)| *index);
 
-        Ok("".to_string())
+        Ok(DownloadReport {
+            files: reports.into_iter().map(|(_, report)| report).collect(),
+        })
     }
 }
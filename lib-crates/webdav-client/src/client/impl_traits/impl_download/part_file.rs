@@ -0,0 +1,314 @@
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_download::verify::{
+    size_mismatch, verify_checksum,
+};
+use crate::client::structs::checksum::{Checksum, strongest};
+use crate::client::structs::friendly_xml::FriendlyResource;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
+
+const PART_SUFFIX: &str = ".part";
+const RECORD_SUFFIX: &str = ".part.json";
+/// 写盘缓冲区大小，网络上收到的小块数据先攒在这里再落盘
+const WRITE_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// 续传记录，和 `.part` 文件放在同一目录
+/// - 只有 URL、大小、ETag/修改时间都和远端一致时才会续传，否则从头下载
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ResumeRecord {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    size: u64,
+    downloaded: u64,
+}
+
+impl ResumeRecord {
+    fn new(resource: &FriendlyResource, size: u64) -> Self {
+        Self {
+            url: resource.full_path.clone(),
+            etag: resource.etag.clone(),
+            last_modified: resource.last_modified.map(|t| t.to_rfc2822()),
+            size,
+            downloaded: 0,
+        }
+    }
+
+    /// 除了已下载字节数以外的字段都一致，才说明是同一个文件的同一个版本
+    fn same_version(&self, other: &Self) -> bool {
+        self.url == other.url
+            && self.etag == other.etag
+            && self.last_modified == other.last_modified
+            && self.size == other.size
+    }
+}
+
+/// 构造 `If-Range` 的值
+/// - 优先用强 ETag，弱 ETag 不能用于 `If-Range`，这时退回到修改时间
+/// - 两者都没有时无法确认远端没变，不允许续传
+fn if_range_validator(resource: &FriendlyResource) -> Option<String> {
+    match &resource.etag {
+        Some(etag) if !etag.is_empty() && !etag.starts_with("W/") => {
+            Some(format!("\"{etag}\""))
+        }
+        _ => resource.last_modified.map(|t| {
+            t.with_timezone(&chrono::Utc)
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string()
+        }),
+    }
+}
+
+/// 下载中的临时文件
+/// - 数据先写进 `<文件名>.part`，进度写进 `<文件名>.part.json`
+/// - 大小校验通过后才原子重命名为最终文件，进程中途退出不会留下"看起来完整"的文件
+pub struct PartFile {
+    output_path: PathBuf,
+    part_path: PathBuf,
+    record_path: PathBuf,
+    record: ResumeRecord,
+    validator: Option<String>,
+    checksums: Vec<Checksum>,
+    file: BufWriter<File>,
+}
+
+impl PartFile {
+    /// 打开（或新建）`output_file_path` 对应的 `.part` 文件
+    /// - 大小未知时不记录进度，总是从头下载
+    pub async fn open(
+        output_file_path: &str,
+        resource: &FriendlyResource,
+    ) -> Result<Self, WebDavClientError> {
+        let output_path = PathBuf::from(output_file_path);
+        let part_path =
+            PathBuf::from(format!("{output_file_path}{PART_SUFFIX}"));
+        let record_path =
+            PathBuf::from(format!("{output_file_path}{RECORD_SUFFIX}"));
+
+        let validator = if_range_validator(resource);
+        let mut record =
+            ResumeRecord::new(resource, resource.size.unwrap_or(0));
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&part_path)
+            .await?;
+
+        let part_len = file.metadata().await?.len();
+
+        let saved = match fs::read(&record_path).await {
+            Ok(bytes) => {
+                serde_json::from_slice::<ResumeRecord>(&bytes).ok()
+            }
+            Err(_) => None,
+        };
+
+        let resumable = resource.size.is_some() && validator.is_some();
+
+        if let Some(saved) = saved
+            && resumable
+            && saved.same_version(&record)
+        {
+            // 记录可能比实际落盘的数据超前（写记录前进程被杀），以较小的为准
+            record.downloaded = saved.downloaded.min(part_len);
+        }
+
+        let part_file = Self {
+            output_path,
+            part_path,
+            record_path,
+            record,
+            validator,
+            checksums: resource.checksums.clone(),
+            file: BufWriter::with_capacity(WRITE_BUFFER_SIZE, file),
+        };
+
+        if part_file.record.downloaded != part_len {
+            part_file
+                .file
+                .get_ref()
+                .set_len(part_file.record.downloaded)
+                .await?;
+        }
+        part_file.save_record().await?;
+
+        Ok(part_file)
+    }
+
+    /// 已经落盘的字节数，也就是续传的起点
+    pub fn downloaded(&self) -> u64 {
+        self.record.downloaded
+    }
+
+    /// `.part` 文件路径，并发分片时各分片自己打开句柄写入
+    pub fn part_path(&self) -> &Path {
+        &self.part_path
+    }
+
+    /// 远端版本的校验值（强 ETag 或修改时间），并发分片时每个请求都带上
+    pub fn validator(&self) -> Option<&str> {
+        self.validator.as_deref()
+    }
+
+    /// 补充响应头里拿到的校验和，已有的算法不覆盖
+    pub fn add_checksums(&mut self, checksums: Vec<Checksum>) {
+        for checksum in checksums {
+            if !self
+                .checksums
+                .iter()
+                .any(|c| c.algorithm == checksum.algorithm)
+            {
+                self.checksums.push(checksum);
+            }
+        }
+    }
+
+    /// 把 `.part` 预分配到 `size` 字节，供并发分片按位置写入
+    /// - 预分配后文件长度不再代表进度，进度只看续传记录
+    pub async fn preallocate(
+        &mut self,
+        size: u64,
+    ) -> Result<(), WebDavClientError> {
+        self.file.flush().await?;
+        let file = self.file.get_ref();
+        if file.metadata().await?.len() < size {
+            file.set_len(size).await?;
+        }
+        Ok(())
+    }
+
+    /// 续传时需要带上的 `If-Range`，从头下载时返回 `None`
+    pub fn if_range(&self) -> Option<&str> {
+        if self.record.downloaded == 0 {
+            return None;
+        }
+        self.validator.as_deref()
+    }
+
+    /// 把写入位置移到 `offset`，之后的 [`write`](Self::write) 从这里顺序写
+    pub async fn seek_to(
+        &mut self,
+        offset: u64,
+    ) -> Result<(), WebDavClientError> {
+        self.file.seek(std::io::SeekFrom::Start(offset)).await?;
+        Ok(())
+    }
+
+    /// 在当前位置顺序写入一段数据（先进缓冲区，`commit` 时才保证落盘）
+    pub async fn write(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), WebDavClientError> {
+        self.file.write_all(bytes).await?;
+        Ok(())
+    }
+
+    /// 数据写入后更新进度
+    /// - 先 flush 再写记录，保证记录里的进度不会超过真正落盘的数据
+    pub async fn commit(
+        &mut self,
+        downloaded: u64,
+    ) -> Result<(), WebDavClientError> {
+        self.file.flush().await?;
+        self.record.downloaded = downloaded;
+        self.save_record().await
+    }
+
+    /// 远端文件已变化（或服务端忽略了 Range），清空重新下载
+    pub async fn reset(&mut self) -> Result<(), WebDavClientError> {
+        self.file.flush().await?;
+        self.file.get_ref().set_len(0).await?;
+        self.commit(0).await
+    }
+
+    async fn save_record(&self) -> Result<(), WebDavClientError> {
+        let json = serde_json::to_vec(&self.record)
+            .map_err(WebDavClientError::SerdeJsonErr)?;
+        fs::write(&self.record_path, json).await?;
+        Ok(())
+    }
+
+    /// 丢弃未完成的下载，删除 `.part` 和续传记录
+    pub async fn discard(self) -> Result<(), WebDavClientError> {
+        drop(self.file);
+        let _ = fs::remove_file(&self.record_path).await;
+        fs::remove_file(&self.part_path).await?;
+        Ok(())
+    }
+
+    /// 校验大小和校验和后重命名为最终文件，并删除续传记录
+    /// - `expected_size` 为 `None` 时跳过大小校验
+    /// - 大小校验失败时保留 `.part` 和记录，下次可以继续
+    /// - `verify` 为 `true` 且服务端给过校验和时，重新读一遍文件比对哈希；
+    ///   不一致说明数据已经损坏，删除 `.part` 和记录，下次从头下载
+    /// - 返回最终文件的大小
+    pub async fn finish(
+        mut self,
+        expected_size: Option<u64>,
+        verify: bool,
+    ) -> Result<u64, WebDavClientError> {
+        self.file.flush().await?;
+        self.file.get_ref().sync_all().await?;
+
+        let actual_size = self.file.get_ref().metadata().await?.len();
+        // 并发分片会预分配文件，只看文件长度不够，还要确认进度确实走完
+        if let Some(expected_size) = expected_size
+            && (actual_size != expected_size
+                || self.record.downloaded != expected_size)
+        {
+            let actual = if actual_size != expected_size {
+                actual_size
+            } else {
+                self.record.downloaded
+            };
+            return Err(size_mismatch(
+                &self.record.url,
+                expected_size,
+                actual,
+            ));
+        }
+
+        if verify
+            && let Some(checksum) = strongest(&self.checksums)
+            && let Err(e) = verify_checksum(
+                &self.part_path,
+                checksum,
+                &self.record.url,
+            )
+            .await
+        {
+            self.discard().await?;
+            return Err(e);
+        }
+
+        drop(self.file);
+        fs::rename(&self.part_path, &self.output_path).await?;
+        let _ = fs::remove_file(&self.record_path).await;
+
+        Ok(actual_size)
+    }
+
+    /// 本地已有完整文件：`output_file_path` 存在、大小和远端一致，且没有未完成的 `.part`
+    pub async fn is_complete(
+        output_file_path: &str,
+        size: Option<u64>,
+    ) -> bool {
+        let Some(size) = size else {
+            return false;
+        };
+        if fs::metadata(format!("{output_file_path}{PART_SUFFIX}"))
+            .await
+            .is_ok()
+        {
+            return false;
+        }
+        matches!(
+            fs::metadata(output_file_path).await,
+            Ok(metadata) if metadata.is_file() && metadata.len() == size
+        )
+    }
+}
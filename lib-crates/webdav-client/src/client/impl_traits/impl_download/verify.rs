@@ -0,0 +1,159 @@
+use crate::client::error::{IntegrityCheck, WebDavClientError};
+use crate::client::structs::checksum::{Checksum, ChecksumHasher};
+use reqwest::Response;
+use reqwest::header::CONTENT_RANGE;
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+/// Nextcloud/ownCloud 在 GET 响应里给出的校验和
+const OC_CHECKSUM: &str = "OC-Checksum";
+/// 计算哈希时每次读取的大小
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+fn mismatch(
+    file_url: &str,
+    check: IntegrityCheck,
+    expected: impl ToString,
+    actual: impl ToString,
+) -> WebDavClientError {
+    WebDavClientError::IntegrityMismatch {
+        url: file_url.to_string(),
+        check,
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+/// 解析 `Content-Range: bytes <start>-<end>/<total|*>`
+fn parse_content_range(value: &str) -> Option<(u64, u64, Option<u64>)> {
+    let range = value.trim().strip_prefix("bytes")?.trim();
+    let (range, total) = range.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let total = match total.trim() {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?, total))
+}
+
+/// 校验 206 响应的 `Content-Range` 和请求的范围一致，返回服务端实际给出的结束位置
+/// - 起点必须等于请求的 `start`，终点不能超过请求的 `end`（没给 `end` 时不能超过文件末尾）
+/// - 服务端给出的总大小和 `total` 不一致，说明远端文件已经变了
+pub fn check_content_range(
+    resp: &Response,
+    file_url: &str,
+    start: u64,
+    end: Option<u64>,
+    total: Option<u64>,
+) -> Result<u64, WebDavClientError> {
+    let requested = match end {
+        Some(end) => format!("bytes {start}-{end}"),
+        None => format!("bytes {start}-"),
+    };
+
+    let header = resp
+        .headers()
+        .get(CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok());
+    let Some((range_start, range_end, range_total)) =
+        header.and_then(parse_content_range)
+    else {
+        return Err(mismatch(
+            file_url,
+            IntegrityCheck::ContentRange,
+            requested,
+            header.unwrap_or("无 Content-Range"),
+        ));
+    };
+
+    let last = end.or(total.map(|total| total.saturating_sub(1)));
+    let range_ok = range_start == start
+        && range_start <= range_end
+        && last.is_none_or(|last| range_end <= last);
+    let total_ok = match (total, range_total) {
+        (Some(total), Some(range_total)) => total == range_total,
+        _ => true,
+    };
+
+    if !range_ok || !total_ok {
+        return Err(mismatch(
+            file_url,
+            IntegrityCheck::ContentRange,
+            requested,
+            header.unwrap_or_default(),
+        ));
+    }
+
+    Ok(range_end)
+}
+
+/// 收到的字节数必须和响应声明的范围一致，否则说明连接中途被截断
+pub fn check_received(
+    file_url: &str,
+    expected: u64,
+    received: u64,
+) -> Result<(), WebDavClientError> {
+    if expected != received {
+        return Err(mismatch(
+            file_url,
+            IntegrityCheck::Size,
+            format!("{expected} 字节"),
+            format!("{received} 字节"),
+        ));
+    }
+    Ok(())
+}
+
+/// 最终文件大小和远端 `FriendlyResource::size` 不一致
+pub fn size_mismatch(
+    file_url: &str,
+    expected: u64,
+    actual: u64,
+) -> WebDavClientError {
+    mismatch(
+        file_url,
+        IntegrityCheck::Size,
+        format!("{expected} 字节"),
+        format!("{actual} 字节"),
+    )
+}
+
+/// 读取响应头里的 `OC-Checksum`
+pub fn response_checksums(resp: &Response) -> Vec<Checksum> {
+    resp.headers()
+        .get(OC_CHECKSUM)
+        .and_then(|value| value.to_str().ok())
+        .map(Checksum::parse_list)
+        .unwrap_or_default()
+}
+
+/// 重新读取已落盘的文件计算哈希，和服务端给出的校验和比对
+pub async fn verify_checksum(
+    path: &Path,
+    checksum: &Checksum,
+    file_url: &str,
+) -> Result<(), WebDavClientError> {
+    let mut file = File::open(path).await?;
+    let mut hasher = ChecksumHasher::new(checksum.algorithm);
+    let mut buffer = vec![0; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    let actual = hasher.finalize();
+    if actual != checksum.value {
+        return Err(mismatch(
+            file_url,
+            IntegrityCheck::Checksum(checksum.algorithm),
+            &checksum.value,
+            actual,
+        ));
+    }
+    Ok(())
+}
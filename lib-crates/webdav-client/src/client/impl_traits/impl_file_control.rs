@@ -0,0 +1,305 @@
+use crate::client::WebDavClient;
+use crate::client::enums::client_enum::Depth;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::get_folders_with_client;
+use crate::client::impl_traits::impl_upload::make_collection::mkcol_with_client;
+use crate::client::impl_traits::impl_url_parse::{
+    as_dir_path, encode_path_segment,
+};
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::file_control::FileControl;
+use crate::client::traits::url_trait::UrlParse;
+use crate::public_enums::WebDavMethod;
+use async_trait::async_trait;
+use reqwest::{Response, StatusCode};
+
+/// 检查写操作的响应状态
+/// - 207 表示部分子项处理失败（比如目录里有文件被锁），同样按失败处理
+async fn check_status(
+    res: Response,
+    action: &str,
+    url: &str,
+) -> Result<(), WebDavClientError> {
+    let status = res.status();
+
+    if status.is_success() && status != StatusCode::MULTI_STATUS {
+        return Ok(());
+    }
+
+    let text = res.text().await.unwrap_or_default();
+    Err(WebDavClientError::String(format!(
+        "{action}失败 {status}: {url} {text}"
+    )))
+}
+
+/// 拒绝直接操作账号根目录，避免一次 DELETE/MOVE 清空整个账号
+fn ensure_not_base_url(
+    web_dav_child_client_key: &WebDavChildClientKey,
+    url: &str,
+) -> Result<(), WebDavClientError> {
+    let base_url = web_dav_child_client_key.get_base_url();
+
+    if url.trim_end_matches('/') == base_url.trim_end_matches('/') {
+        return Err(WebDavClientError::ParseUrlErr(
+            "禁止对账号根目录执行该操作".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// 取 URL 的上级目录（带尾部斜杠）
+fn parent_url(url: &str) -> &str {
+    let trimmed = url.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(idx) => &trimmed[..=idx],
+        None => trimmed,
+    }
+}
+
+fn overwrite_header(overwrite: bool) -> &'static str {
+    if overwrite { "T" } else { "F" }
+}
+
+pub async fn delete_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    force: bool,
+) -> Result<(), WebDavClientError> {
+    let method = WebDavMethod::DELETE.try_into()?;
+
+    let res = http_client.send(http_client.request(method, url)).await?;
+
+    if force && res.status() == StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+
+    check_status(res, "删除", url).await
+}
+
+/// 发送 MOVE/COPY
+/// - `to_url` 必须已经过 `format_url_path` 校验，这里只负责组装请求
+pub async fn transfer_with_client(
+    http_client: &WebDavHttpClient,
+    method: WebDavMethod,
+    from_url: &str,
+    to_url: &str,
+    overwrite: bool,
+) -> Result<(), WebDavClientError> {
+    if from_url.trim_end_matches('/') == to_url.trim_end_matches('/') {
+        return Err(WebDavClientError::String(
+            "源路径与目标路径相同".to_string(),
+        ));
+    }
+
+    let action = method.to_string();
+    let method = method.try_into()?;
+
+    let request = http_client
+        .request(method, from_url)
+        .header("Destination", to_url)
+        .header("Overwrite", overwrite_header(overwrite));
+    let res = http_client.send(request).await?;
+
+    check_status(res, &action, from_url).await
+}
+
+impl WebDavClient {
+    /// 校验 MOVE/COPY 的源和目标，返回两者的完整 URL
+    async fn format_transfer_urls(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        from_path: &str,
+        to_path: &str,
+    ) -> Result<(String, String), WebDavClientError> {
+        let from_url = self
+            .format_url_path(web_dav_child_client_key, from_path)
+            .await?;
+        let to_url = self
+            .format_url_path(web_dav_child_client_key, to_path)
+            .await?;
+
+        ensure_not_base_url(web_dav_child_client_key, &from_url)?;
+        ensure_not_base_url(web_dav_child_client_key, &to_url)?;
+
+        Ok((from_url, to_url))
+    }
+}
+
+#[async_trait]
+impl FileControl for WebDavClient {
+    async fn mkdir(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        parent_path: &str,
+        dir_name: &str,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        if let Some(profile) = http_client.profile() {
+            profile.check_name(dir_name)?;
+        }
+
+        let dir_path = format!(
+            "{}{}/",
+            as_dir_path(parent_path),
+            encode_path_segment(dir_name)
+        );
+
+        let url = self
+            .format_url_path(web_dav_child_client_key, &dir_path)
+            .await?;
+
+        mkcol_with_client(&http_client, &url).await?;
+
+        Ok(())
+    }
+
+    async fn rm_file(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        file_path: &str,
+        force: bool,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url = self
+            .format_url_path(web_dav_child_client_key, file_path)
+            .await?;
+
+        ensure_not_base_url(web_dav_child_client_key, &url)?;
+
+        delete_with_client(&http_client, &url, force).await
+    }
+
+    async fn rmdir(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        dir_path: &str,
+        force: bool,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url = self
+            .format_url_path(
+                web_dav_child_client_key,
+                &as_dir_path(dir_path),
+            )
+            .await?;
+
+        ensure_not_base_url(web_dav_child_client_key, &url)?;
+
+        if !force {
+            // 除了目录自身以外还有别的 response，说明目录非空
+            let multi_status =
+                get_folders_with_client(&http_client, &url, &Depth::One)
+                    .await?;
+
+            if multi_status.responses.len() > 1 {
+                return Err(WebDavClientError::String(format!(
+                    "目录非空，拒绝删除: {url}"
+                )));
+            }
+        }
+
+        delete_with_client(&http_client, &url, force).await
+    }
+
+    async fn rename(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        file_path: &str,
+        new_name: &str,
+    ) -> Result<(), WebDavClientError> {
+        if new_name.is_empty() || new_name == "." || new_name == ".." {
+            return Err(WebDavClientError::String(format!(
+                "非法的文件名: {new_name}"
+            )));
+        }
+
+        if let Some(profile) = self
+            .try_get_client_entity(web_dav_child_client_key)
+            .await?
+            .profile()
+        {
+            profile.check_name(new_name)?;
+        }
+
+        let from_url = self
+            .format_url_path(web_dav_child_client_key, file_path)
+            .await?;
+
+        let mut to_url = format!(
+            "{}{}",
+            parent_url(&from_url),
+            encode_path_segment(new_name)
+        );
+        if from_url.ends_with('/') {
+            to_url.push('/');
+        }
+
+        // 目标同样走一遍校验，防止对根目录重命名时越界
+        self.move_item(web_dav_child_client_key, &from_url, &to_url, false)
+            .await
+    }
+
+    async fn move_item(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        from_path: &str,
+        to_path: &str,
+        overwrite: bool,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let (from_url, to_url) = self
+            .format_transfer_urls(
+                web_dav_child_client_key,
+                from_path,
+                to_path,
+            )
+            .await?;
+
+        transfer_with_client(
+            &http_client,
+            WebDavMethod::MOVE,
+            &from_url,
+            &to_url,
+            overwrite,
+        )
+        .await
+    }
+
+    async fn copy_item(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        from_path: &str,
+        to_path: &str,
+        overwrite: bool,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let (from_url, to_url) = self
+            .format_transfer_urls(
+                web_dav_child_client_key,
+                from_path,
+                to_path,
+            )
+            .await?;
+
+        transfer_with_client(
+            &http_client,
+            WebDavMethod::COPY,
+            &from_url,
+            &to_url,
+            overwrite,
+        )
+        .await
+    }
+}
@@ -1,27 +1,31 @@
 use crate::client::WebDavClient;
 use crate::client::enums::client_enum::Depth;
 use crate::client::error::WebDavClientError;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::multi_status_stream::MultiStatusParser;
+use crate::client::structs::propfind::{
+    PropName, PropfindRequest, TDeadProps,
+};
 use crate::client::structs::raw_xml::MultiStatus;
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
-use crate::client::traits::folder::{Folder, TFileMetas, TFolders};
+use crate::client::traits::folder::{
+    Folder, TFileMetas, TFolders, TResponseStream,
+};
 use crate::client::traits::url_trait::UrlParse;
 use crate::public_enums::WebDavMethod;
 use async_trait::async_trait;
 use futures_util::future::join_all;
-use quick_xml::de::from_str;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use reqwest::Response;
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
 
-pub async fn get_folders_with_client(
-    http_client: &reqwest::Client,
+/// 发送 PROPFIND，返回还没读取响应体的响应
+async fn send_propfind(
+    http_client: &WebDavHttpClient,
     url: &str,
     depth: &Depth,
-) -> Result<MultiStatus, WebDavClientError> {
-    // WebDAV PROPFIND 请求体
-    let propfind_body = r#"<?xml version="1.0" encoding="utf-8" ?>
-<D:propfind xmlns:D="DAV:">
-  <D:allprop/>
-</D:propfind>"#;
-
+    propfind_request: &PropfindRequest,
+) -> Result<Response, WebDavClientError> {
     // 组装请求头
     let mut headers = HeaderMap::new();
     headers
@@ -32,18 +36,130 @@ pub async fn get_folders_with_client(
     let method = WebDavMethod::PROPFIND.try_into()?;
 
     // 发送 PROPFIND 到基准目录（已保证有尾部斜杠）
-    let res = http_client
+    let request = http_client
         .request(method, url)
         .headers(headers)
-        .body(propfind_body)
-        .send()
-        .await?;
+        .body(propfind_request.to_xml());
+    http_client.send(request).await
+}
 
-    let status = res.status();
+/// 按 [`PropfindRequest`] 发送 PROPFIND
+pub async fn propfind_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    depth: &Depth,
+    propfind_request: &PropfindRequest,
+) -> Result<MultiStatus, WebDavClientError> {
+    let res =
+        send_propfind(http_client, url, depth, propfind_request).await?;
+    read_multi_status(res).await
+}
+
+/// 按 [`PropfindRequest`] 发送 PROPFIND，边收边解析，见 [`read_multi_status_stream`]
+pub async fn propfind_stream_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    depth: &Depth,
+    propfind_request: &PropfindRequest,
+) -> Result<TResponseStream, WebDavClientError> {
+    let res =
+        send_propfind(http_client, url, depth, propfind_request).await?;
+    read_multi_status_stream(res).await
+}
+
+/// `allprop` 的请求体
+/// - 服务商配置了校验和属性时用 `include` 一并请求，有的服务端 allprop 不返回它们
+fn allprop_request(http_client: &WebDavHttpClient) -> PropfindRequest {
+    let checksum_props = http_client
+        .profile()
+        .map(|profile| profile.checksum_props.as_slice())
+        .unwrap_or_default();
+
+    PropfindRequest::allprop().with_props(
+        checksum_props.iter().map(|prop| PropName::parse_clark(prop)),
+    )
+}
+
+/// `allprop` 的 PROPFIND
+pub async fn get_folders_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    depth: &Depth,
+) -> Result<MultiStatus, WebDavClientError> {
+    let propfind_request = allprop_request(http_client);
+    propfind_with_client(http_client, url, depth, &propfind_request).await
+}
+
+/// `allprop` 的 PROPFIND，返回还没检查状态码的响应，供需要区分失败原因的调用方使用
+pub async fn send_allprop_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    depth: &Depth,
+) -> Result<Response, WebDavClientError> {
+    let propfind_request = allprop_request(http_client);
+    send_propfind(http_client, url, depth, &propfind_request).await
+}
+
+/// `allprop` 的 PROPFIND，边收边解析
+pub async fn get_folders_stream_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    depth: &Depth,
+) -> Result<TResponseStream, WebDavClientError> {
+    let propfind_request = allprop_request(http_client);
+    propfind_stream_with_client(http_client, url, depth, &propfind_request)
+        .await
+}
 
-    let xml_text = res.text().await?;
+/// 读取资源上指定的自定义属性（Depth 0）
+/// - 只返回服务端有值的属性，不存在的属性（404 的 `propstat`）不出现在结果里
+pub async fn get_custom_props_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    prop_names: &[PropName],
+) -> Result<TDeadProps, WebDavClientError> {
+    let propfind_request =
+        PropfindRequest::prop().with_props(prop_names.iter().cloned());
+
+    let multi_status = propfind_with_client(
+        http_client,
+        url,
+        &Depth::Zero,
+        &propfind_request,
+    )
+    .await?;
+
+    Ok(multi_status
+        .responses
+        .into_iter()
+        .flat_map(|response| response.propstats)
+        .filter(|propstat| propstat.is_success())
+        .flat_map(|propstat| propstat.prop.dead_props)
+        .filter(|(prop_name, _)| prop_names.contains(prop_name))
+        .collect())
+}
+
+/// 读取 207 Multi-Status 响应并解析为 [`MultiStatus`]
+/// - PROPFIND、SEARCH 等返回多状态的请求共用
+/// - 响应体按块解析，不会先把整个响应体读成字符串
+pub async fn read_multi_status(
+    res: Response,
+) -> Result<MultiStatus, WebDavClientError> {
+    let responses =
+        read_multi_status_stream(res).await?.try_collect().await?;
+    Ok(MultiStatus { responses })
+}
+
+/// 边收边解析 207 Multi-Status 响应，每收到一个完整的 `<response>` 就返回一项
+/// - 状态码不是 2xx/207 时读出响应体作为错误信息
+/// - 响应体出错（连接中断、XML 不完整）时返回一条 `Err` 后结束
+pub async fn read_multi_status_stream(
+    res: Response,
+) -> Result<TResponseStream, WebDavClientError> {
+    let status = res.status();
 
     if !status.is_success() && status.as_u16() != 207 {
+        let xml_text = res.text().await?;
         return Err(WebDavClientError::String(format!(
             "状态解析异常 {status}: {xml}",
             status = status,
@@ -51,10 +167,30 @@ pub async fn get_folders_with_client(
         )));
     }
 
-    let multi_status: MultiStatus = from_str(&xml_text)
-        .map_err(|e| WebDavClientError::SerdeErr(e.to_string()))?;
+    let body = res.bytes_stream();
+    let state = Some((body, MultiStatusParser::new()));
 
-    Ok(multi_status)
+    Ok(stream::unfold(state, |state| async move {
+        let (mut body, mut parser) = state?;
+        loop {
+            match parser.next_response() {
+                Ok(Some(response)) => {
+                    return Some((Ok(response), Some((body, parser))));
+                }
+                Ok(None) => {}
+                Err(e) => return Some((Err(e), None)),
+            }
+
+            match body.next().await {
+                Some(Ok(chunk)) => parser.feed(&chunk),
+                Some(Err(e)) => return Some((Err(e.into()), None)),
+                None => {
+                    return parser.finish().err().map(|e| (Err(e), None));
+                }
+            }
+        }
+    })
+    .boxed())
 }
 
 #[async_trait]
@@ -76,6 +212,53 @@ impl Folder for WebDavClient {
         Ok(result)
     }
 
+    async fn get_folders_stream(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        depth: &Depth,
+    ) -> Result<TResponseStream, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        get_folders_stream_with_client(&http_client, &url, depth).await
+    }
+
+    async fn propfind(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        depth: &Depth,
+        propfind_request: &PropfindRequest,
+    ) -> Result<MultiStatus, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        propfind_with_client(&http_client, &url, depth, propfind_request)
+            .await
+    }
+
+    async fn get_custom_props(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        prop_names: &[PropName],
+    ) -> Result<TDeadProps, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        get_custom_props_with_client(&http_client, &url, prop_names).await
+    }
+
     async fn collect_folders(
         &self,
         key: &WebDavChildClientKey,
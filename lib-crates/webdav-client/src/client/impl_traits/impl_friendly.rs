@@ -1,5 +1,7 @@
 use crate::client::error::WebDavClientError;
 use crate::client::structs::friendly_xml::FriendlyResource;
+#[cfg(feature = "friendly-xml")]
+use crate::client::structs::quota::{FriendlyQuota, QuotaInfo};
 use crate::client::structs::raw_xml::MultiStatus;
 #[cfg(feature = "friendly-xml")]
 use crate::public_traits::friendly::FriendlyXml;
@@ -17,3 +19,10 @@ impl FriendlyXml<FriendlyResource, TFriendlyWebDavFilesXml>
         FriendlyResource::new(self.to_owned())
     }
 }
+
+#[cfg(feature = "friendly-xml")]
+impl FriendlyXml<FriendlyQuota, FriendlyQuota> for QuotaInfo {
+    fn to_friendly(&self) -> Result<FriendlyQuota, WebDavClientError> {
+        Ok(FriendlyQuota::new(self))
+    }
+}
@@ -0,0 +1,270 @@
+use crate::client::WebDavClient;
+use crate::client::enums::client_enum::Depth;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::propfind_with_client;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::lock::{
+    LockInfo, LockOptions, timeout_header,
+};
+use crate::client::structs::propfind::PropfindRequest;
+use crate::client::structs::raw_xml::Prop;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::lock::Lock;
+use crate::client::traits::url_trait::UrlParse;
+use crate::public_enums::WebDavMethod;
+use async_trait::async_trait;
+use reqwest::Response;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+/// 从 LOCK 响应里取锁信息
+/// - 令牌优先取 `Lock-Token` 响应头，刷新时响应头里没有，用已知的令牌
+/// - 响应体里找不到对应的 `activelock` 时返回 `None`，由调用方按请求参数补全
+async fn read_lock_response(
+    res: Response,
+    action: &str,
+    url: &str,
+    known_token: Option<&str>,
+) -> Result<(String, Option<LockInfo>), WebDavClientError> {
+    let status = res.status();
+
+    let header_token = res
+        .headers()
+        .get("Lock-Token")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        });
+
+    let text = res.text().await.unwrap_or_default();
+
+    if !status.is_success() {
+        return Err(WebDavClientError::String(format!(
+            "{action}失败 {status}: {url} {text}"
+        )));
+    }
+
+    let token = header_token
+        .or_else(|| known_token.map(str::to_string))
+        .ok_or_else(|| {
+        WebDavClientError::String(format!(
+            "{action}响应里没有锁令牌: {url}"
+        ))
+    })?;
+
+    let info = Prop::from_xml(&text)
+        .ok()
+        .and_then(|prop| prop.lock_discovery)
+        .and_then(|discovery| {
+            discovery.active_locks.iter().find_map(|active_lock| {
+                LockInfo::from_active_lock(active_lock, url)
+                    .filter(|info| info.token == token)
+            })
+        });
+
+    Ok((token, info))
+}
+
+/// 加锁并记到账号上
+pub async fn lock_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    lock_options: &LockOptions,
+) -> Result<LockInfo, WebDavClientError> {
+    if lock_options.depth == Depth::One {
+        return Err(WebDavClientError::String(
+            "LOCK 只支持 Depth 0 或 infinity".to_string(),
+        ));
+    }
+
+    let method = WebDavMethod::LOCK.try_into()?;
+    let request = http_client
+        .request(method, url)
+        .header(CONTENT_TYPE, "application/xml")
+        .header("Depth", lock_options.depth.as_str())
+        .header("Timeout", timeout_header(lock_options.timeout))
+        .body(lock_options.to_xml());
+    let res = http_client.send(request).await?;
+
+    let (token, info) = read_lock_response(res, "加锁", url, None).await?;
+
+    // 自己加的锁，锁住的就是请求地址；不用服务端 lockroot 的编码，方便之后按 URL 查找
+    let info = match info {
+        Some(info) => LockInfo { url: url.to_string(), ..info },
+        None => LockInfo {
+            url: url.to_string(),
+            token,
+            scope: lock_options.scope,
+            depth: lock_options.depth,
+            owner: lock_options.owner.clone(),
+            timeout: lock_options.timeout,
+        },
+    };
+
+    http_client.locks.insert(info.clone());
+    Ok(info)
+}
+
+/// 刷新账号持有的锁：不带请求体的 LOCK + `If: (<token>)`
+pub async fn refresh_lock_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    timeout: Option<Duration>,
+) -> Result<LockInfo, WebDavClientError> {
+    let held = http_client.locks.find(url).ok_or_else(|| {
+        WebDavClientError::String(format!("没有持有该路径的锁: {url}"))
+    })?;
+
+    let method = WebDavMethod::LOCK.try_into()?;
+    let request = http_client
+        .request(method, &held.url)
+        .header("If", format!("(<{}>)", held.token))
+        .header("Timeout", timeout_header(timeout));
+    let res = http_client.send(request).await?;
+
+    let (_, info) =
+        read_lock_response(res, "刷新锁", &held.url, Some(&held.token))
+            .await?;
+
+    let info = match info {
+        Some(info) => LockInfo { url: held.url, ..info },
+        None => LockInfo { timeout, ..held },
+    };
+
+    http_client.locks.insert(info.clone());
+    Ok(info)
+}
+
+/// 释放账号持有的锁，成功后从账号上移除
+pub async fn unlock_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+) -> Result<(), WebDavClientError> {
+    let held = http_client.locks.find(url).ok_or_else(|| {
+        WebDavClientError::String(format!("没有持有该路径的锁: {url}"))
+    })?;
+
+    let method = WebDavMethod::UNLOCK.try_into()?;
+    let request = http_client
+        .request(method, &held.url)
+        .header("Lock-Token", format!("<{}>", held.token));
+    let res = http_client.send(request).await?;
+
+    let status = res.status();
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(WebDavClientError::String(format!(
+            "解锁失败 {status}: {url} {text}"
+        )));
+    }
+
+    http_client.locks.remove(&held.token);
+    Ok(())
+}
+
+/// 通过 `lockdiscovery` 查询资源上的锁
+pub async fn discover_locks_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+) -> Result<Vec<LockInfo>, WebDavClientError> {
+    let propfind_request =
+        PropfindRequest::prop().with_dav_prop("lockdiscovery");
+
+    let multi_status = propfind_with_client(
+        http_client,
+        url,
+        &Depth::Zero,
+        &propfind_request,
+    )
+    .await?;
+
+    Ok(multi_status
+        .responses
+        .iter()
+        .flat_map(|response| &response.propstats)
+        .filter(|propstat| propstat.is_success())
+        .filter_map(|propstat| propstat.prop.lock_discovery.as_ref())
+        .flat_map(|discovery| &discovery.active_locks)
+        .filter_map(|active_lock| {
+            LockInfo::from_active_lock(active_lock, url)
+        })
+        .collect())
+}
+
+#[async_trait]
+impl Lock for WebDavClient {
+    async fn lock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        lock_options: Option<LockOptions>,
+    ) -> Result<LockInfo, WebDavClientError> {
+        let lock_options =
+            lock_options.unwrap_or(LockOptions::new_default_config());
+
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        lock_with_client(&http_client, &url, &lock_options).await
+    }
+
+    async fn refresh_lock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        timeout: Option<Duration>,
+    ) -> Result<LockInfo, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        refresh_lock_with_client(&http_client, &url, timeout).await
+    }
+
+    async fn unlock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<(), WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        unlock_with_client(&http_client, &url).await
+    }
+
+    async fn held_locks(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<Vec<LockInfo>, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        Ok(http_client.locks.list())
+    }
+
+    async fn discover_locks(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<Vec<LockInfo>, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        discover_locks_with_client(&http_client, &url).await
+    }
+}
@@ -0,0 +1,67 @@
+use crate::client::WebDavClient;
+use crate::client::error::WebDavClientError;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::impl_raw_xml::collect_responses;
+use crate::client::structs::proppatch::{
+    PropPatchRequest, PropPatchResult,
+};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::properties::Properties;
+use crate::client::traits::url_trait::UrlParse;
+use crate::public_enums::WebDavMethod;
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+
+/// 发送 PROPPATCH 并整理每个属性的结果
+pub async fn proppatch_with_client(
+    http_client: &WebDavHttpClient,
+    url: &str,
+    proppatch_request: &PropPatchRequest,
+) -> Result<PropPatchResult, WebDavClientError> {
+    if proppatch_request.is_empty() {
+        return Err(WebDavClientError::String(format!(
+            "PROPPATCH 没有要修改的属性: {url}"
+        )));
+    }
+
+    let method = WebDavMethod::PROPPATCH.try_into()?;
+    let request = http_client
+        .request(method, url)
+        .header(CONTENT_TYPE, "application/xml")
+        .body(proppatch_request.to_xml());
+    let res = http_client.send(request).await?;
+
+    let status = res.status();
+    let text = res.text().await?;
+
+    if !status.is_success() {
+        return Err(WebDavClientError::String(format!(
+            "修改属性失败 {status}: {url} {text}"
+        )));
+    }
+
+    if status.as_u16() != 207 || text.trim().is_empty() {
+        return Ok(PropPatchResult::all_success(url, proppatch_request));
+    }
+
+    let responses = collect_responses(&text)?;
+    Ok(PropPatchResult::from_responses(url, proppatch_request, &responses))
+}
+
+#[async_trait]
+impl Properties for WebDavClient {
+    async fn proppatch(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        proppatch_request: &PropPatchRequest,
+    ) -> Result<PropPatchResult, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        proppatch_with_client(&http_client, &url, proppatch_request).await
+    }
+}
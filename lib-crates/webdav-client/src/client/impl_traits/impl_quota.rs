@@ -0,0 +1,133 @@
+use crate::client::WebDavClient;
+use crate::client::enums::client_enum::Depth;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::propfind_with_client;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::propfind::{
+    OC_NAMESPACE, PropName, PropfindRequest,
+};
+use crate::client::structs::quota::QuotaInfo;
+use crate::client::structs::raw_xml::{MultiStatus, Prop};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::quota::Quota;
+use crate::client::traits::url_trait::UrlParse;
+use async_trait::async_trait;
+
+/// Nextcloud/ownCloud 的 `quota-available-bytes` 用 `-3` 表示没有上限
+const UNLIMITED_QUOTA: i64 = -3;
+
+/// RFC 4331 配额属性 + Nextcloud/ownCloud 的目录大小
+fn quota_request() -> PropfindRequest {
+    PropfindRequest::prop()
+        .with_quota_props()
+        .with_prop(OC_NAMESPACE, "size")
+}
+
+/// 从 Depth 0 的结果里取配额，没有 RFC 4331 属性时返回 `None`
+/// - `quota-available-bytes` 为负数时（Nextcloud/ownCloud 的未知、未计算、无上限）不当作可用空间
+fn parse_quota(
+    url: &str,
+    multi_status: MultiStatus,
+    from_root: bool,
+) -> Option<QuotaInfo> {
+    let oc_size = PropName::new(OC_NAMESPACE, "size");
+
+    let mut used = None;
+    let mut available = None;
+    let mut size = None;
+
+    for propstat in multi_status
+        .responses
+        .into_iter()
+        .take(1)
+        .flat_map(|response| response.propstats)
+        .filter(|propstat| propstat.is_success())
+    {
+        let Prop {
+            quota_used_bytes,
+            quota_available_bytes,
+            mut dead_props,
+            ..
+        } = propstat.prop;
+
+        used = used.or(quota_used_bytes);
+        available = available.or(quota_available_bytes);
+        size = size.or(dead_props.remove(&oc_size));
+    }
+
+    if used.is_none() && available.is_none() {
+        return None;
+    }
+
+    let available = available.and_then(|a| a.trim().parse::<i64>().ok());
+
+    Some(QuotaInfo {
+        url: url.to_string(),
+        used_bytes: used
+            .or(size)
+            .and_then(|used| used.trim().parse::<u64>().ok()),
+        available_bytes: available
+            .and_then(|available| u64::try_from(available).ok()),
+        unlimited: available == Some(UNLIMITED_QUOTA),
+        from_root,
+    })
+}
+
+/// 查询配额，子目录没有配额属性时退回账号根目录
+pub async fn get_quota_with_client(
+    http_client: &WebDavHttpClient,
+    base_url: &str,
+    url: &str,
+) -> Result<QuotaInfo, WebDavClientError> {
+    let multi_status = propfind_with_client(
+        http_client,
+        url,
+        &Depth::Zero,
+        &quota_request(),
+    )
+    .await?;
+
+    if let Some(quota) = parse_quota(url, multi_status, false) {
+        return Ok(quota);
+    }
+
+    if url.trim_end_matches('/') != base_url.trim_end_matches('/') {
+        let multi_status = propfind_with_client(
+            http_client,
+            base_url,
+            &Depth::Zero,
+            &quota_request(),
+        )
+        .await?;
+
+        if let Some(quota) = parse_quota(url, multi_status, true) {
+            return Ok(quota);
+        }
+    }
+
+    Err(WebDavClientError::String(format!(
+        "服务端没有返回配额信息: {url}"
+    )))
+}
+
+#[async_trait]
+impl Quota for WebDavClient {
+    async fn get_quota(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<QuotaInfo, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let url =
+            self.format_url_path(web_dav_child_client_key, path).await?;
+
+        get_quota_with_client(
+            &http_client,
+            &web_dav_child_client_key.get_base_url(),
+            &url,
+        )
+        .await
+    }
+}
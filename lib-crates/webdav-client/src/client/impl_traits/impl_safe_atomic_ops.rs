@@ -1,5 +1,7 @@
 use crate::client::WebDavClient;
 use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_capabilities::load_capabilities;
+use crate::client::structs::auth::AuthStrategy;
 use crate::client::structs::webdav_child_client::{
     WebDavChildClientKey, WebDavChildClientValue,
 };
@@ -13,17 +15,37 @@ impl SafeAtomicOps for WebDavClient {
         base_url: &str,
         username: &str,
         password: &str,
+    ) -> Result<WebDavChildClientKey, WebDavClientError> {
+        self.add_account_with_auth(
+            base_url,
+            username,
+            AuthStrategy::basic(username, password),
+        )
+    }
+
+    fn add_account_with_auth(
+        &mut self,
+        base_url: &str,
+        username: &str,
+        auth: AuthStrategy,
     ) -> Result<WebDavChildClientKey, WebDavClientError> {
         let webdav_child_client_key = WebDavChildClientKey::new(
             &base_url.to_string(),
             &username.to_string(),
         )?;
 
-        let webdav_child_client_value = WebDavChildClientValue::new(
+        let profile = self
+            .providers
+            .match_url(&webdav_child_client_key.get_base_url())
+            .cloned();
+
+        let webdav_child_client_value = WebDavChildClientValue::with_auth(
             &webdav_child_client_key.get_base_url(),
             &username,
-            &password,
-        )?;
+            auth,
+            profile,
+        )?
+        .with_retry_policy(self.retry_policy.clone());
 
         let _ = &webdav_child_client_value.get_base_url(); // 读取一次避免Strut那里报未使用警告
 
@@ -31,12 +53,38 @@ impl SafeAtomicOps for WebDavClient {
         {
             println!("新增的账号地址：{}", &base_url.to_string());
             println!("新增的key：{:?}", webdav_child_client_key);
+            println!(
+                "认证方式：{}",
+                webdav_child_client_value.get_auth_scheme()
+            );
+            println!(
+                "服务商配置：{:?}",
+                webdav_child_client_value
+                    .get_profile()
+                    .map(|profile| &profile.name)
+            );
             println!(
                 "新增的value: {:?}",
                 webdav_child_client_value.get_encrypted_username()
             )
         }
 
+        // 有运行时的话在后台先探测一次服务端能力，失败时等第一次用到再探测
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let http_client = webdav_child_client_value.client.clone();
+            let cache = webdav_child_client_value.capabilities.clone();
+            let base_url = webdav_child_client_key.get_base_url();
+            runtime.spawn(async move {
+                let _ = load_capabilities(
+                    &http_client,
+                    &base_url,
+                    &cache,
+                    false,
+                )
+                .await;
+            });
+        }
+
         self.clients.insert(
             webdav_child_client_key.to_owned(),
             webdav_child_client_value.into(),
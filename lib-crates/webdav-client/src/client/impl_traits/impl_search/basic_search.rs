@@ -0,0 +1,133 @@
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::read_multi_status;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::raw_xml::MultiStatus;
+use crate::client::traits::search::SearchQuery;
+use crate::public_enums::WebDavMethod;
+use chrono::{DateTime, FixedOffset, Utc};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+
+/// 转义 XML 文本节点中的特殊字符
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// 转义 `like` 的通配符，RFC 5323 规定转义符为 `\`
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// 转成 RFC 1123 格式，这是 `getlastmodified` 的标准格式
+fn http_date(date: &DateTime<FixedOffset>) -> String {
+    date.with_timezone(&Utc)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn compare(op: &str, prop: &str, literal: &str) -> String {
+    format!(
+        "<D:{op}><D:prop><D:{prop}/></D:prop><D:literal>{}</D:literal></D:{op}>",
+        escape_xml(literal)
+    )
+}
+
+/// 把 [`SearchQuery`] 转成 basicsearch 的 `<D:where>` 条件
+fn build_where(query: &SearchQuery) -> String {
+    let mut conditions = Vec::new();
+
+    if !query.keyword.is_empty() {
+        conditions.push(format!(
+            "<D:like caseless=\"yes\"><D:prop><D:displayname/></D:prop><D:literal>%{}%</D:literal></D:like>",
+            escape_xml(&escape_like(&query.keyword))
+        ));
+    }
+    if let Some(content_type) = &query.content_type {
+        conditions.push(format!(
+            "<D:like><D:prop><D:getcontenttype/></D:prop><D:literal>{}%</D:literal></D:like>",
+            escape_xml(&escape_like(content_type))
+        ));
+    }
+    if let Some(min_size) = query.min_size {
+        conditions.push(compare(
+            "gte",
+            "getcontentlength",
+            &min_size.to_string(),
+        ));
+    }
+    if let Some(max_size) = query.max_size {
+        conditions.push(compare(
+            "lte",
+            "getcontentlength",
+            &max_size.to_string(),
+        ));
+    }
+    if let Some(after) = &query.modified_after {
+        conditions.push(compare(
+            "gte",
+            "getlastmodified",
+            &http_date(after),
+        ));
+    }
+    if let Some(before) = &query.modified_before {
+        conditions.push(compare(
+            "lte",
+            "getlastmodified",
+            &http_date(before),
+        ));
+    }
+
+    match conditions.len() {
+        0 => String::new(),
+        1 => format!("<D:where>{}</D:where>", conditions[0]),
+        _ => format!(
+            "<D:where><D:and>{}</D:and></D:where>",
+            conditions.concat()
+        ),
+    }
+}
+
+/// 组装 RFC 5323 `searchrequest` 请求体
+fn build_search_body(scope_url: &str, query: &SearchQuery) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:searchrequest xmlns:D="DAV:">
+  <D:basicsearch>
+    <D:select><D:allprop/></D:select>
+    <D:from>
+      <D:scope>
+        <D:href>{scope}</D:href>
+        <D:depth>infinity</D:depth>
+      </D:scope>
+    </D:from>
+    {condition}
+  </D:basicsearch>
+</D:searchrequest>"#,
+        scope = escape_xml(scope_url),
+        condition = build_where(query)
+    )
+}
+
+/// 以 `scope_url` 为范围发送 SEARCH 请求
+pub async fn basic_search_with_client(
+    http_client: &WebDavHttpClient,
+    scope_url: &str,
+    query: &SearchQuery,
+) -> Result<MultiStatus, WebDavClientError> {
+    let mut headers = HeaderMap::new();
+    headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/xml"));
+
+    let method = WebDavMethod::SEARCH.try_into()?;
+
+    let request = http_client
+        .request(method, scope_url)
+        .headers(headers)
+        .body(build_search_body(scope_url, query));
+    let res = http_client.send(request).await?;
+
+    read_multi_status(res).await
+}
@@ -0,0 +1,119 @@
+mod basic_search;
+
+use crate::client::WebDavClient;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_search::basic_search::basic_search_with_client;
+use crate::client::impl_traits::impl_url_parse::as_dir_path;
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::capabilities::Capabilities;
+use crate::client::traits::search::{
+    SearchConfig, SearchFile, SearchQuery,
+};
+use crate::client::traits::url_trait::UrlParse;
+use async_trait::async_trait;
+
+/// 客户端侧的条件匹配
+/// - 回退搜索靠它筛选；SEARCH 的结果也再过一遍，抹平各家服务端大小写、前缀匹配上的差异
+fn matches_query(
+    resource: &FriendlyResource,
+    query: &SearchQuery,
+) -> bool {
+    if !query.keyword.is_empty()
+        && !resource
+            .name
+            .to_lowercase()
+            .contains(&query.keyword.to_lowercase())
+    {
+        return false;
+    }
+
+    if let Some(content_type) = &query.content_type {
+        match &resource.mime {
+            Some(mime) if mime.starts_with(content_type.as_str()) => {}
+            _ => return false,
+        }
+    }
+
+    if query.min_size.is_some() || query.max_size.is_some() {
+        let Some(size) = resource.size else {
+            return false;
+        };
+        if query.min_size.is_some_and(|min| size < min)
+            || query.max_size.is_some_and(|max| size > max)
+        {
+            return false;
+        }
+    }
+
+    if query.modified_after.is_some() || query.modified_before.is_some() {
+        let Some(modified) = resource.last_modified else {
+            return false;
+        };
+        if query.modified_after.is_some_and(|after| modified < after)
+            || query
+                .modified_before
+                .is_some_and(|before| modified > before)
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[async_trait]
+impl SearchFile for WebDavClient {
+    async fn search(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        query: &SearchQuery,
+        search_config: Option<SearchConfig>,
+    ) -> Result<Vec<FriendlyResource>, WebDavClientError> {
+        let search_config =
+            search_config.unwrap_or(SearchConfig::new_default_config());
+
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let scope_url = self
+            .format_url_path(web_dav_child_client_key, &as_dir_path(path))
+            .await?;
+
+        // 探测失败不影响搜索，直接走回退逻辑
+        let supports_search = self
+            .get_capabilities(web_dav_child_client_key)
+            .await
+            .is_ok_and(|capabilities| capabilities.supports_search);
+
+        if supports_search {
+            let multi_status =
+                basic_search_with_client(&http_client, &scope_url, query)
+                    .await?;
+
+            let resources = FriendlyResource::new(multi_status)?;
+
+            return Ok(resources
+                .into_iter()
+                .filter(|r| matches_query(r, query))
+                .collect());
+        }
+
+        // 回退为遍历整棵目录树，在客户端筛选
+        let tree = self
+            .walk_tree_of(
+                web_dav_child_client_key,
+                &scope_url,
+                search_config.max_concurrency,
+            )
+            .await?;
+
+        Ok(tree
+            .entries
+            .into_iter()
+            .map(|entry| entry.resource)
+            .filter(|r| matches_query(r, query))
+            .collect())
+    }
+}
@@ -0,0 +1,554 @@
+use crate::client::WebDavClient;
+use crate::client::enums::client_enum::Depth;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_folder::{
+    get_folders_with_client, read_multi_status, send_allprop_with_client,
+};
+use crate::client::impl_traits::impl_url_parse::{
+    as_dir_path, resolve_href,
+};
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::raw_xml::Response;
+use crate::client::structs::remote_tree::{
+    RemoteEntry, RemoteTree, TreeWalkMethod,
+};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::capabilities::Capabilities;
+use crate::client::traits::folder::{Folder, TResponseStream};
+use crate::client::traits::tree_walk::{
+    DEFAULT_WALK_CONCURRENCY, TResourceStream, TreeWalk, WalkConfig,
+};
+use crate::client::traits::url_trait::UrlParse;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use futures_util::stream::{self, FuturesUnordered};
+use percent_encoding::percent_decode_str;
+use reqwest::{StatusCode, Url};
+use std::collections::{HashSet, VecDeque};
+
+/// 按片段解码的路径，避免服务端和本地对同一个字符的编码（大小写、是否转义）不同
+fn decoded_segments(url: &Url) -> Vec<String> {
+    url.path()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            percent_decode_str(segment).decode_utf8_lossy().to_string()
+        })
+        .collect()
+}
+
+/// 整理成树里的一项；根目录自身和根目录之外的 href 返回 `None`
+fn to_entry(
+    root_segments: &[String],
+    dir_url: &Url,
+    mut resource: FriendlyResource,
+) -> Result<Option<RemoteEntry>, WebDavClientError> {
+    let mut url = resolve_href(dir_url, &resource.full_path)?;
+    if resource.is_dir && !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    let segments = decoded_segments(&url);
+    let relative_path = match segments.strip_prefix(root_segments) {
+        Some(rest) if !rest.is_empty() => rest,
+        _ => return Ok(None),
+    };
+
+    // 相对路径会拼到本地目录下，解码后不能跳出去
+    if relative_path.iter().any(|segment| {
+        segment == "." || segment == ".." || segment.contains(['/', '\\'])
+    }) {
+        return Err(WebDavClientError::String(format!(
+            "服务端返回了不安全的路径: {}",
+            resource.full_path
+        )));
+    }
+    let relative_path = relative_path.join("/");
+
+    resource.full_path = url.to_string();
+    Ok(Some(RemoteEntry { url: url.to_string(), relative_path, resource }))
+}
+
+/// 列出单个目录，返回目录 URL 以及它的直接子项
+async fn list_directory(
+    http_client: &WebDavHttpClient,
+    dir_url: Url,
+) -> Result<(Url, Vec<FriendlyResource>), WebDavClientError> {
+    let multi_status = get_folders_with_client(
+        http_client,
+        dir_url.as_str(),
+        &Depth::One,
+    )
+    .await?;
+    let resources = FriendlyResource::new(multi_status)?;
+    Ok((dir_url, resources))
+}
+
+/// 一次 `Depth: infinity` 请求的结果
+enum InfinityResult {
+    /// 请求成功，是否真的展开由调用方判断
+    Entries(Vec<RemoteEntry>),
+    /// 服务端明确拒绝：403，或响应体里带 `propfind-finite-depth`
+    Refused,
+    /// 超时、5xx 等其他错误，说明不了是否支持
+    Failed,
+}
+
+/// 一次 `Depth: infinity` 拿到整棵树
+async fn walk_depth_infinity(
+    http_client: &WebDavHttpClient,
+    root_url: &Url,
+    root_segments: &[String],
+) -> Result<InfinityResult, WebDavClientError> {
+    let Ok(res) = send_allprop_with_client(
+        http_client,
+        root_url.as_str(),
+        &Depth::Infinity,
+    )
+    .await
+    else {
+        return Ok(InfinityResult::Failed);
+    };
+
+    let status = res.status();
+    if status == StatusCode::FORBIDDEN {
+        return Ok(InfinityResult::Refused);
+    }
+    if !status.is_success() {
+        let body = res.text().await.unwrap_or_default();
+        return Ok(if body.contains("propfind-finite-depth") {
+            InfinityResult::Refused
+        } else {
+            InfinityResult::Failed
+        });
+    }
+
+    let Ok(multi_status) = read_multi_status(res).await else {
+        return Ok(InfinityResult::Failed);
+    };
+
+    let mut entries = Vec::new();
+    for resource in FriendlyResource::new(multi_status)? {
+        if let Some(entry) = to_entry(root_segments, root_url, resource)? {
+            entries.push(entry);
+        }
+    }
+    Ok(InfinityResult::Entries(entries))
+}
+
+/// 逐个目录 `Depth: 1`，同时最多 `max_concurrency` 个请求
+async fn walk_parallel(
+    http_client: &WebDavHttpClient,
+    root_url: &Url,
+    root_segments: &[String],
+    max_concurrency: usize,
+) -> Result<Vec<RemoteEntry>, WebDavClientError> {
+    let max_concurrency = max_concurrency.max(1);
+
+    let mut pending = VecDeque::from([root_url.clone()]);
+    let mut in_flight = FuturesUnordered::new();
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    loop {
+        while in_flight.len() < max_concurrency {
+            match pending.pop_front() {
+                Some(dir_url) => {
+                    in_flight.push(list_directory(http_client, dir_url))
+                }
+                None => break,
+            }
+        }
+
+        let (dir_url, children) = match in_flight.next().await {
+            Some(result) => result?,
+            None => break,
+        };
+
+        for child in children {
+            let Some(entry) = to_entry(root_segments, &dir_url, child)?
+            else {
+                continue;
+            };
+
+            // PROPFIND 的结果里包含目录自身，同一项只收一次
+            if !seen.insert(entry.relative_path.clone()) {
+                continue;
+            }
+
+            if entry.resource.is_dir {
+                let child_url = Url::parse(&entry.url).map_err(|e| {
+                    WebDavClientError::ParseUrlErr(e.to_string())
+                })?;
+                pending.push_back(child_url);
+            }
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// 列出 `dir_url` 下的整棵树
+/// - `try_depth_infinity` 时先试一次 `Depth: infinity`，失败或没有展开时退回逐层遍历
+/// - 条目按相对路径排序，目录排在它的子项前面
+pub async fn walk_tree_with_client(
+    http_client: &WebDavHttpClient,
+    dir_url: &str,
+    try_depth_infinity: bool,
+    max_concurrency: usize,
+) -> Result<RemoteTree, WebDavClientError> {
+    walk_tree_probing(
+        http_client,
+        dir_url,
+        try_depth_infinity,
+        max_concurrency,
+    )
+    .await
+    .map(|(tree, _)| tree)
+}
+
+/// 同 [`walk_tree_with_client`]，另外返回这次能确定的 `Depth: infinity` 支持情况
+/// - 服务端明确拒绝（403 `propfind-finite-depth`）时为 `Some(false)`，其他错误为 `None`
+/// - 确实展开到子目录以下时为 `Some(true)`
+/// - 有子目录但没有展开时逐层遍历确认：子目录里有东西说明 infinity 被当成了 1，为 `Some(false)`
+/// - 没有子目录，或子目录都是空的时看不出来，为 `None`
+async fn walk_tree_probing(
+    http_client: &WebDavHttpClient,
+    dir_url: &str,
+    try_depth_infinity: bool,
+    max_concurrency: usize,
+) -> Result<(RemoteTree, Option<bool>), WebDavClientError> {
+    let root_url = Url::parse(&as_dir_path(dir_url))
+        .map_err(|e| WebDavClientError::ParseUrlErr(e.to_string()))?;
+    let root_segments = decoded_segments(&root_url);
+
+    // 第三项：infinity 的结果有子目录但没有展开，要靠逐层遍历确认
+    let (infinity, mut depth_infinity, unexpanded) = if try_depth_infinity
+    {
+        match walk_depth_infinity(http_client, &root_url, &root_segments)
+            .await?
+        {
+            InfinityResult::Entries(entries) => {
+                let has_dirs =
+                    entries.iter().any(|entry| entry.resource.is_dir);
+                let expanded =
+                    entries.iter().any(|entry| entry.depth() > 1);
+                match (has_dirs, expanded) {
+                    (_, true) => (Some(entries), Some(true), false),
+                    (false, false) => (Some(entries), None, false),
+                    // 有的服务端把 infinity 当成 1，和子目录都是空的分不清
+                    (true, false) => (None, None, true),
+                }
+            }
+            InfinityResult::Refused => (None, Some(false), false),
+            InfinityResult::Failed => (None, None, false),
+        }
+    } else {
+        (None, None, false)
+    };
+
+    let (method, mut entries) = match infinity {
+        Some(entries) => (TreeWalkMethod::DepthInfinity, entries),
+        None => {
+            let entries = walk_parallel(
+                http_client,
+                &root_url,
+                &root_segments,
+                max_concurrency,
+            )
+            .await?;
+
+            // 逐层遍历找到了更深的项，说明前面的 infinity 没有展开
+            if unexpanded && entries.iter().any(|entry| entry.depth() > 1)
+            {
+                depth_infinity = Some(false);
+            }
+            (TreeWalkMethod::Parallel, entries)
+        }
+    };
+
+    entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    Ok((
+        RemoteTree { root_url: root_url.to_string(), method, entries },
+        depth_infinity,
+    ))
+}
+
+/// 通配符匹配：`*` 不跨 `/`，`**` 可以跨，`**/` 也可以匹配零层目录，`?` 匹配单个非 `/` 字符
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            (rest.first() == Some(&'/')
+                && wildcard_match(&rest[1..], text))
+                || (0..=text.len())
+                    .any(|i| wildcard_match(rest, &text[i..]))
+        }
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| wildcard_match(rest, &text[i..])),
+        ['?', rest @ ..] => text.split_first().is_some_and(|(c, text)| {
+            *c != '/' && wildcard_match(rest, text)
+        }),
+        [c, rest @ ..] => text
+            .split_first()
+            .is_some_and(|(t, text)| t == c && wildcard_match(rest, text)),
+    }
+}
+
+/// 不含 `/` 的模式只看文件名，否则看相对路径
+fn matches_any(patterns: &[String], entry: &RemoteEntry) -> bool {
+    patterns.iter().any(|pattern| {
+        let target = if pattern.contains('/') {
+            entry.relative_path.as_str()
+        } else {
+            entry.relative_path.rsplit('/').next().unwrap_or_default()
+        };
+        let pattern: Vec<char> = pattern.chars().collect();
+        let target: Vec<char> = target.chars().collect();
+        wildcard_match(&pattern, &target)
+    })
+}
+
+/// 流式遍历的状态，目录按深度优先的顺序逐个列出
+struct WalkStream<'a, F: ?Sized> {
+    folder: &'a F,
+    web_dav_child_client_key: WebDavChildClientKey,
+    walk_config: WalkConfig,
+    /// 还没解析成 URL 的起始路径，第一次拉取时解析
+    start_path: Option<String>,
+    root_segments: Vec<String>,
+    /// 待列出的目录及其层级，栈顶先列
+    pending: Vec<(Url, usize)>,
+    /// 正在读取的目录及其层级
+    listing: Option<(Url, usize)>,
+    /// 正在读取的目录的 PROPFIND 响应，边收边解析
+    responses: Option<TResponseStream>,
+    /// 正在读取的目录里的子目录，读完后压栈
+    subdirs: Vec<(Url, usize)>,
+}
+
+impl<'a, F> WalkStream<'a, F>
+where
+    F: Folder + UrlParse + Sync + ?Sized,
+{
+    async fn next_item(
+        &mut self,
+    ) -> Option<Result<FriendlyResource, WebDavClientError>> {
+        loop {
+            if let Some(path) = self.start_path.take() {
+                if let Err(e) = self.start(&path).await {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+
+            let Some(responses) = self.responses.as_mut() else {
+                let (dir_url, depth) = self.pending.pop()?;
+                match self
+                    .folder
+                    .get_folders_stream(
+                        &self.web_dav_child_client_key,
+                        dir_url.as_str(),
+                        &Depth::One,
+                    )
+                    .await
+                {
+                    Ok(responses) => {
+                        self.responses = Some(responses);
+                        self.listing = Some((dir_url, depth));
+                    }
+                    Err(e) => return Some(Err(e)),
+                }
+                continue;
+            };
+
+            match responses.next().await {
+                Some(Ok(response)) => match self.accept(response) {
+                    Ok(Some(resource)) => return Some(Ok(resource)),
+                    Ok(None) => {}
+                    Err(e) => return Some(Err(e)),
+                },
+                Some(Err(e)) => {
+                    self.finish_listing();
+                    return Some(Err(e));
+                }
+                None => self.finish_listing(),
+            }
+        }
+    }
+
+    async fn start(
+        &mut self,
+        path: &str,
+    ) -> Result<(), WebDavClientError> {
+        let root_url = self
+            .folder
+            .format_url_path(
+                &self.web_dav_child_client_key,
+                &as_dir_path(path),
+            )
+            .await?;
+        let root_url = Url::parse(&root_url)
+            .map_err(|e| WebDavClientError::ParseUrlErr(e.to_string()))?;
+
+        self.root_segments = decoded_segments(&root_url);
+        self.pending.push((root_url, 0));
+        Ok(())
+    }
+
+    /// 当前目录读完（或出错），已经发现的子目录倒序压栈，保持服务端返回的顺序
+    fn finish_listing(&mut self) {
+        self.responses = None;
+        self.listing = None;
+        self.pending.extend(self.subdirs.drain(..).rev());
+    }
+
+    /// 处理当前目录里的一项：子目录记下来，符合条件的返回
+    fn accept(
+        &mut self,
+        response: Response,
+    ) -> Result<Option<FriendlyResource>, WebDavClientError> {
+        let (Some((dir_url, depth)), Some(resource)) =
+            (&self.listing, FriendlyResource::from_response(response))
+        else {
+            return Ok(None);
+        };
+
+        let Some(entry) =
+            to_entry(&self.root_segments, dir_url, resource)?
+        else {
+            return Ok(None);
+        };
+
+        let WalkConfig { max_depth, include, exclude, include_dirs } =
+            &self.walk_config;
+
+        // 跳过目录自身，以及把 Depth 当成 infinity 的服务端多给的项
+        if entry.depth() != depth + 1
+            || max_depth.is_some_and(|max| entry.depth() > max)
+            || matches_any(exclude, &entry)
+        {
+            return Ok(None);
+        }
+
+        if entry.resource.is_dir
+            && max_depth.is_none_or(|max| entry.depth() < max)
+        {
+            let child_url = Url::parse(&entry.url).map_err(|e| {
+                WebDavClientError::ParseUrlErr(e.to_string())
+            })?;
+            self.subdirs.push((child_url, entry.depth()));
+        }
+
+        let included = (*include_dirs || !entry.resource.is_dir)
+            && (include.is_empty() || matches_any(include, &entry));
+        Ok(included.then_some(entry.resource))
+    }
+}
+
+/// 基于 [`Folder::get_folders_stream`] 的流式遍历，见 [`TreeWalk::walk_stream`]
+pub fn walk_folder_stream<'a, F>(
+    folder: &'a F,
+    web_dav_child_client_key: &WebDavChildClientKey,
+    path: &str,
+    walk_config: WalkConfig,
+) -> TResourceStream<'a>
+where
+    F: Folder + UrlParse + Sync + ?Sized,
+{
+    let state = WalkStream {
+        folder,
+        web_dav_child_client_key: web_dav_child_client_key.clone(),
+        walk_config,
+        start_path: Some(path.to_string()),
+        root_segments: Vec::new(),
+        pending: Vec::new(),
+        listing: None,
+        responses: None,
+        subdirs: Vec::new(),
+    };
+
+    stream::unfold(state, |mut state| async move {
+        let item = state.next_item().await?;
+        Some((item, state))
+    })
+    .boxed()
+}
+
+impl WebDavClient {
+    /// 按账号缓存的能力决定是否先试 `Depth: infinity`，试过且能确定时记下结果
+    pub(crate) async fn walk_tree_of(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        dir_url: &str,
+        max_concurrency: usize,
+    ) -> Result<RemoteTree, WebDavClientError> {
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        // 探测失败时按允许处理，被拒绝也只是多一个请求
+        let try_depth_infinity = self
+            .get_capabilities(web_dav_child_client_key)
+            .await
+            .map(|capabilities| capabilities.allows_depth_infinity())
+            .unwrap_or(true);
+
+        let (tree, depth_infinity) = walk_tree_probing(
+            &http_client,
+            dir_url,
+            try_depth_infinity,
+            max_concurrency,
+        )
+        .await?;
+
+        if let Some(supported) = depth_infinity {
+            self.record_depth_infinity(
+                web_dav_child_client_key,
+                supported,
+            )
+            .await?;
+        }
+
+        Ok(tree)
+    }
+}
+
+#[async_trait]
+impl TreeWalk for WebDavClient {
+    async fn walk_tree(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        max_concurrency: Option<usize>,
+    ) -> Result<RemoteTree, WebDavClientError> {
+        let dir_url = self
+            .format_url_path(web_dav_child_client_key, &as_dir_path(path))
+            .await?;
+
+        self.walk_tree_of(
+            web_dav_child_client_key,
+            &dir_url,
+            max_concurrency.unwrap_or(DEFAULT_WALK_CONCURRENCY),
+        )
+        .await
+    }
+
+    fn walk_stream<'a>(
+        &'a self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        walk_config: WalkConfig,
+    ) -> TResourceStream<'a> {
+        walk_folder_stream(
+            self,
+            web_dav_child_client_key,
+            path,
+            walk_config,
+        )
+    }
+}
@@ -0,0 +1,272 @@
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_upload::make_collection::mkcol_with_client;
+use crate::client::impl_traits::impl_upload::upload_file::upload_file;
+use crate::client::impl_traits::impl_upload::upload_plan::{
+    TUploadItems, UploadItem,
+};
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::progress::Progress;
+use crate::client::structs::transfer_control::TransferControl;
+use crate::client::structs::transfer_pool::TransferPool;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::download::ThreadMode;
+use crate::client::traits::upload::{UploadConfig, UploadFileReport};
+use futures_util::StreamExt;
+use futures_util::stream::FuturesUnordered;
+use std::path::Path;
+
+/// 一次上传调用里所有条目共用的参数
+struct UploadContext<'a> {
+    transfer_pool: &'a TransferPool,
+    web_dav_child_client_key: &'a WebDavChildClientKey,
+    http_client: &'a WebDavHttpClient,
+    overwrite: bool,
+    preserve_mtime: bool,
+    progress: Progress,
+    control: TransferControl,
+}
+
+/// 上传单个条目，目录走 MKCOL，文件走 PUT
+async fn upload_item(
+    http_client: &WebDavHttpClient,
+    item: UploadItem,
+    overwrite: bool,
+    preserve_mtime: bool,
+    progress: &Progress,
+    control: &TransferControl,
+) -> UploadFileReport {
+    let UploadItem { local_path, remote_url, is_dir, size } = item;
+    let local_path = local_path.to_string_lossy().to_string();
+
+    let (bytes, result, mtime_preserved) = if is_dir {
+        let result =
+            mkcol_with_client(http_client, &remote_url).await.map(|_| ());
+        (0, result, false)
+    } else {
+        let file_progress =
+            progress.start_file(&remote_url, &local_path, Some(size), 0);
+
+        match upload_file(
+            http_client,
+            Path::new(&local_path),
+            &remote_url,
+            overwrite,
+            preserve_mtime,
+            file_progress.clone(),
+            control.clone(),
+        )
+        .await
+        {
+            Ok((bytes, mtime_preserved)) => {
+                file_progress.finish();
+                (bytes, Ok(()), mtime_preserved)
+            }
+            Err(e) => {
+                file_progress.fail(&e);
+                (0, Err(e), false)
+            }
+        }
+    };
+
+    UploadFileReport {
+        local_path,
+        remote_url,
+        is_dir,
+        bytes,
+        result,
+        mtime_preserved,
+    }
+}
+
+/// 条目没能开始上传（或任务异常退出、被取消）时的失败记录
+fn failed_report(
+    item: UploadItem,
+    error: WebDavClientError,
+    progress: &Progress,
+) -> UploadFileReport {
+    let local_path = item.local_path.to_string_lossy().to_string();
+    if !item.is_dir {
+        progress.fail_file(&item.remote_url, &local_path, &error);
+    }
+
+    UploadFileReport {
+        local_path,
+        remote_url: item.remote_url,
+        is_dir: item.is_dir,
+        bytes: 0,
+        result: Err(error),
+        mtime_preserved: false,
+    }
+}
+
+/// 串行上传
+/// - `use_pool` 为 `true` 时每个条目先拿传输池的许可；目录（MKCOL）很轻，不占用许可
+/// - 暂停时在条目之间等待，取消后剩下的条目都记为取消
+async fn upload_single_thread(
+    context: &UploadContext<'_>,
+    items: TUploadItems,
+    use_pool: bool,
+) -> Vec<UploadFileReport> {
+    let mut reports = Vec::with_capacity(items.len());
+    for item in items {
+        if let Err(e) = context.control.checkpoint().await {
+            reports.push(failed_report(item, e, &context.progress));
+            continue;
+        }
+
+        let permit = if use_pool {
+            match context
+                .transfer_pool
+                .acquire(context.web_dav_child_client_key)
+                .await
+            {
+                Ok(permit) => Some(permit),
+                Err(e) => {
+                    reports.push(failed_report(
+                        item,
+                        e,
+                        &context.progress,
+                    ));
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        reports.push(
+            upload_item(
+                context.http_client,
+                item,
+                context.overwrite,
+                context.preserve_mtime,
+                &context.progress,
+                &context.control,
+            )
+            .await,
+        );
+        drop(permit);
+    }
+    reports
+}
+
+/// 并行上传
+/// - 每个文件是传输池 spawn 出来的独立任务，本次调用最多 `max_concurrency` 个文件在途
+/// - 暂停时不再派发新文件，取消后没派发的文件都记为取消
+async fn upload_multi_thread(
+    context: &UploadContext<'_>,
+    files: TUploadItems,
+    max_concurrency: usize,
+) -> Vec<UploadFileReport> {
+    let progress = &context.progress;
+    let max_concurrency = max_concurrency.max(1);
+    let mut files = files.into_iter();
+    let mut in_flight = FuturesUnordered::new();
+    let mut reports = Vec::new();
+
+    loop {
+        while in_flight.len() < max_concurrency {
+            let Some(item) = files.next() else {
+                break;
+            };
+
+            if let Err(e) = context.control.checkpoint().await {
+                reports.push(failed_report(item, e, progress));
+                continue;
+            }
+
+            // 任务异常退出时用来补一条失败记录
+            let fallback = item.clone();
+
+            let client = context.http_client.clone();
+            let overwrite = context.overwrite;
+            let preserve_mtime = context.preserve_mtime;
+            let task_progress = progress.clone();
+            let task_control = context.control.clone();
+            let task = async move {
+                upload_item(
+                    &client,
+                    item,
+                    overwrite,
+                    preserve_mtime,
+                    &task_progress,
+                    &task_control,
+                )
+                .await
+            };
+
+            match context
+                .transfer_pool
+                .spawn(context.web_dav_child_client_key, task)
+                .await
+            {
+                Ok(handle) => in_flight.push(async move {
+                    handle.await.unwrap_or_else(|e| {
+                        failed_report(fallback, e, progress)
+                    })
+                }),
+                Err(e) => {
+                    reports.push(failed_report(fallback, e, progress))
+                }
+            }
+        }
+
+        match in_flight.next().await {
+            Some(report) => reports.push(report),
+            None => break,
+        }
+    }
+
+    reports
+}
+
+/// 执行上传计划
+/// - 目录总是按计划顺序串行创建（父目录在前），之后再按线程模式上传文件
+pub async fn handle_upload(
+    transfer_pool: &TransferPool,
+    web_dav_child_client_key: &WebDavChildClientKey,
+    http_client: &WebDavHttpClient,
+    items: TUploadItems,
+    upload_config: &UploadConfig,
+) -> Vec<UploadFileReport> {
+    let UploadConfig {
+        thread_mode,
+        overwrite,
+        max_concurrency,
+        progress,
+        control,
+        preserve_mtime,
+    } = upload_config;
+
+    let (dirs, files): (TUploadItems, TUploadItems) =
+        items.into_iter().partition(|item| item.is_dir);
+
+    let context = UploadContext {
+        transfer_pool,
+        web_dav_child_client_key,
+        http_client,
+        overwrite: *overwrite,
+        preserve_mtime: *preserve_mtime,
+        progress: Progress::new(progress.clone()),
+        control: control.clone().unwrap_or_default(),
+    };
+    context.progress.add_files(files.iter().map(|item| Some(item.size)));
+
+    let mut reports = upload_single_thread(&context, dirs, false).await;
+
+    // 自动模式：多个文件才走并行
+    let multi_thread = match thread_mode {
+        ThreadMode::SingleThread => false,
+        ThreadMode::MultipleThread => true,
+        ThreadMode::Auto => files.len() > 1,
+    };
+
+    let file_reports = if multi_thread {
+        upload_multi_thread(&context, files, *max_concurrency).await
+    } else {
+        upload_single_thread(&context, files, true).await
+    };
+
+    reports.extend(file_reports);
+    reports
+}
@@ -0,0 +1,56 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::public_enums::WebDavMethod;
+use reqwest::StatusCode;
+
+/// 对 `dir_url` 发送 MKCOL
+/// - 返回 `true` 表示新建成功，`false` 表示目录本来就存在（405）
+/// - `dir_url` 必须以 `/` 结尾，否则部分服务端会当成文件处理
+pub async fn mkcol_with_client(
+    http_client: &WebDavHttpClient,
+    dir_url: &str,
+) -> Result<bool, WebDavClientError> {
+    let method = WebDavMethod::MKCOL.try_into()?;
+
+    let res =
+        http_client.send(http_client.request(method, dir_url)).await?;
+
+    let status = res.status();
+
+    match status {
+        StatusCode::CREATED | StatusCode::OK => Ok(true),
+        // RFC 4918：目标已存在时返回 405
+        StatusCode::METHOD_NOT_ALLOWED => Ok(false),
+        _ => {
+            let text = res.text().await.unwrap_or_default();
+            Err(WebDavClientError::String(format!(
+                "创建目录失败 {status}: {dir_url} {text}"
+            )))
+        }
+    }
+}
+
+/// 确保 `dir_url` 以及它和 `base_url` 之间的每一级目录都存在
+/// - 两个参数都必须是已编码的完整 URL，且 `dir_url` 以 `base_url` 开头
+/// - 从浅到深逐级 MKCOL，避免父目录不存在时返回 409
+pub async fn ensure_collection_with_client(
+    http_client: &WebDavHttpClient,
+    base_url: &str,
+    dir_url: &str,
+) -> Result<(), WebDavClientError> {
+    let relative = dir_url.strip_prefix(base_url).ok_or_else(|| {
+        WebDavClientError::ParseUrlErr(
+            "路径越界，禁止访问上级目录".to_string(),
+        )
+    })?;
+
+    let mut current = base_url.to_string();
+
+    for segment in relative.split('/').filter(|s| !s.is_empty()) {
+        current.push_str(segment);
+        current.push('/');
+        mkcol_with_client(http_client, &current).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+mod handle_upload;
+pub mod make_collection;
+mod upload_file;
+mod upload_plan;
+
+use crate::client::WebDavClient;
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_upload::handle_upload::handle_upload;
+use crate::client::impl_traits::impl_upload::make_collection::ensure_collection_with_client;
+use crate::client::impl_traits::impl_upload::upload_plan::collect_upload_items;
+use crate::client::impl_traits::impl_url_parse::as_dir_path;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::upload::{Upload, UploadConfig, UploadReport};
+use crate::client::traits::url_trait::UrlParse;
+use async_trait::async_trait;
+
+#[async_trait]
+impl Upload for WebDavClient {
+    async fn upload_files(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        local_paths: Vec<String>,
+        remote_dir: &str,
+        upload_config: Option<UploadConfig>,
+    ) -> Result<UploadReport, WebDavClientError> {
+        let upload_config =
+            upload_config.unwrap_or(UploadConfig::new_default_config());
+
+        let http_client =
+            self.try_get_client_entity(web_dav_child_client_key).await?;
+
+        let remote_dir_url = self
+            .format_url_path(
+                web_dav_child_client_key,
+                &as_dir_path(remote_dir),
+            )
+            .await?;
+
+        ensure_collection_with_client(
+            &http_client,
+            &web_dav_child_client_key.get_base_url(),
+            &remote_dir_url,
+        )
+        .await?;
+
+        let (items, failed_items) = collect_upload_items(
+            &local_paths,
+            &remote_dir_url,
+            http_client.profile(),
+        )
+        .await;
+
+        let mut files = handle_upload(
+            &self.transfer_pool,
+            web_dav_child_client_key,
+            &http_client,
+            items,
+            &upload_config,
+        )
+        .await;
+        files.extend(failed_items);
+
+        Ok(UploadReport { files })
+    }
+}
@@ -0,0 +1,153 @@
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_properties::proppatch_with_client;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::progress::FileProgress;
+use crate::client::structs::propfind::PropName;
+use crate::client::structs::proppatch::PropPatchRequest;
+use crate::client::structs::transfer_control::TransferControl;
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use reqwest::Body;
+use reqwest::header::{CONTENT_LENGTH, IF_NONE_MATCH};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+/// 每次从磁盘读取并发送的块大小
+const READ_BUFFER_SIZE: usize = 256 * 1024;
+
+/// 把文件包装成流式请求体，边读边发，不会把整个文件读进内存
+/// - 每读出一块就记一次进度
+/// - 每读一块前检查暂停/取消，取消时让请求体出错，从而中断上传
+fn file_body(
+    file: File,
+    file_progress: FileProgress,
+    control: TransferControl,
+) -> Body {
+    let body_stream = stream::unfold(Some(file), move |file| {
+        let file_progress = file_progress.clone();
+        let control = control.clone();
+        async move {
+            let mut file = file?;
+            if let Err(e) = control.checkpoint().await {
+                return Some((
+                    Err(std::io::Error::other(e.to_string())),
+                    None,
+                ));
+            }
+            let mut buf = vec![0u8; READ_BUFFER_SIZE];
+            match file.read(&mut buf).await {
+                Ok(0) => None,
+                Ok(n) => {
+                    buf.truncate(n);
+                    file_progress.add(n as u64);
+                    Some((Ok(buf), Some(file)))
+                }
+                // 读盘出错后不再继续读，交给 hyper 中断请求
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    });
+
+    Body::wrap_stream(body_stream)
+}
+
+/// 上传后用 PROPPATCH 写入服务商配置的修改时间属性，返回是否全部写入成功
+/// - 文件已经上传完成，这里失败不影响上传结果
+async fn set_remote_mtime(
+    http_client: &WebDavHttpClient,
+    remote_url: &str,
+    mtime: SystemTime,
+) -> bool {
+    let mtime_props = http_client
+        .profile()
+        .map(|profile| profile.mtime_props.as_slice())
+        .unwrap_or_default();
+
+    if mtime_props.is_empty() {
+        return false;
+    }
+
+    let http_date = DateTime::<Utc>::from(mtime)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+
+    let proppatch_request = mtime_props.iter().fold(
+        PropPatchRequest::new(),
+        |request, prop| {
+            request.with_set_prop(PropName::parse_clark(prop), &http_date)
+        },
+    );
+
+    proppatch_with_client(http_client, remote_url, &proppatch_request)
+        .await
+        .is_ok_and(|result| result.is_success())
+}
+
+/// 以 PUT 上传单个文件，返回发送的字节数和远端是否保留了本地修改时间
+/// - 显式带上 `Content-Length`，不少 WebDAV 服务端不接受 chunked 上传
+/// - `preserve_mtime` 时按服务商配置带上 `X-OC-Mtime`，服务端没有接受再走 PROPPATCH
+pub async fn upload_file(
+    http_client: &WebDavHttpClient,
+    local_path: &Path,
+    remote_url: &str,
+    overwrite: bool,
+    preserve_mtime: bool,
+    file_progress: FileProgress,
+    control: TransferControl,
+) -> Result<(u64, bool), WebDavClientError> {
+    let file = File::open(local_path).await?;
+    let metadata = file.metadata().await?;
+    let size = metadata.len();
+    let mtime = metadata.modified().ok().filter(|_| preserve_mtime);
+
+    let mut request = http_client
+        .put(remote_url)
+        .header(CONTENT_LENGTH, size)
+        .body(file_body(file, file_progress, control.clone()));
+
+    if !overwrite {
+        request = request.header(IF_NONE_MATCH, "*");
+    }
+
+    if let Some(mtime) = mtime
+        && http_client.profile().is_some_and(|p| p.oc_mtime_header)
+        && let Ok(since_epoch) = mtime.duration_since(UNIX_EPOCH)
+    {
+        request = request.header("X-OC-Mtime", since_epoch.as_secs());
+    }
+
+    let res = match http_client.send(request).await {
+        Ok(res) => res,
+        // 请求体因为取消而中断时，报告取消而不是网络错误
+        Err(_) if control.is_cancelled() => {
+            return Err(WebDavClientError::Cancelled);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let status = res.status();
+
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(WebDavClientError::String(format!(
+            "上传失败 {status}: {remote_url} {text}"
+        )));
+    }
+
+    let mtime_accepted = res
+        .headers()
+        .get("X-OC-MTime")
+        .is_some_and(|value| value.as_bytes() == b"accepted");
+
+    let mtime_preserved = match mtime {
+        Some(_) if mtime_accepted => true,
+        Some(mtime) => {
+            set_remote_mtime(http_client, remote_url, mtime).await
+        }
+        None => false,
+    };
+
+    Ok((size, mtime_preserved))
+}
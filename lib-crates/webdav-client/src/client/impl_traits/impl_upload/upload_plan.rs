@@ -0,0 +1,160 @@
+use crate::client::error::WebDavClientError;
+use crate::client::impl_traits::impl_url_parse::encode_path_segment;
+use crate::client::structs::provider_profile::ProviderProfile;
+use crate::client::traits::upload::UploadFileReport;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// 待上传的单个条目
+#[derive(Clone)]
+pub struct UploadItem {
+    pub local_path: PathBuf,
+    /// 远端完整 URL，目录以 `/` 结尾
+    pub remote_url: String,
+    pub is_dir: bool,
+    /// 本地文件大小，目录为 0
+    pub size: u64,
+}
+
+pub type TUploadItems = Vec<UploadItem>;
+type TFailedItems = Vec<UploadFileReport>;
+
+fn failed_item(
+    local_path: &Path,
+    remote_url: String,
+    is_dir: bool,
+    err: WebDavClientError,
+) -> UploadFileReport {
+    UploadFileReport {
+        local_path: local_path.to_string_lossy().to_string(),
+        remote_url,
+        is_dir,
+        bytes: 0,
+        result: Err(err),
+        mtime_preserved: false,
+    }
+}
+
+/// 展开本地路径，生成上传计划
+/// - 按层序遍历，保证目录总是排在它的子项前面，MKCOL 时父目录已经存在
+/// - `remote_dir_url` 必须是以 `/` 结尾的完整 URL
+/// - 无法读取的本地路径直接进入失败列表，不影响其他条目
+/// - 有服务商配置时，远端名字里不允许的字符会被替换，超过大小上限的文件直接记为失败
+pub async fn collect_upload_items(
+    local_paths: &[String],
+    remote_dir_url: &str,
+    profile: Option<&ProviderProfile>,
+) -> (TUploadItems, TFailedItems) {
+    let mut items = Vec::new();
+    let mut failed = Vec::new();
+
+    let mut queue: VecDeque<(PathBuf, String)> = local_paths
+        .iter()
+        .map(|p| (PathBuf::from(p), remote_dir_url.to_string()))
+        .collect();
+
+    while let Some((local_path, parent_url)) = queue.pop_front() {
+        let name = match local_path.file_name() {
+            Some(name) => {
+                let name = name.to_string_lossy();
+                match profile {
+                    Some(profile) => {
+                        encode_path_segment(&profile.sanitize_name(&name))
+                    }
+                    None => encode_path_segment(&name),
+                }
+            }
+            None => {
+                failed.push(failed_item(
+                    &local_path,
+                    parent_url,
+                    false,
+                    WebDavClientError::String(
+                        "无法从本地路径中取得文件名".to_string(),
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        let metadata = match fs::metadata(&local_path).await {
+            Ok(m) => m,
+            Err(e) => {
+                failed.push(failed_item(
+                    &local_path,
+                    format!("{parent_url}{name}"),
+                    false,
+                    e.into(),
+                ));
+                continue;
+            }
+        };
+
+        if !metadata.is_dir() {
+            if let Some(Err(e)) =
+                profile.map(|p| p.check_file_size(metadata.len()))
+            {
+                failed.push(failed_item(
+                    &local_path,
+                    format!("{parent_url}{name}"),
+                    false,
+                    e,
+                ));
+                continue;
+            }
+
+            items.push(UploadItem {
+                local_path,
+                remote_url: format!("{parent_url}{name}"),
+                is_dir: false,
+                size: metadata.len(),
+            });
+            continue;
+        }
+
+        let dir_url = format!("{parent_url}{name}/");
+
+        let mut read_dir = match fs::read_dir(&local_path).await {
+            Ok(r) => r,
+            Err(e) => {
+                failed.push(failed_item(
+                    &local_path,
+                    dir_url,
+                    true,
+                    e.into(),
+                ));
+                continue;
+            }
+        };
+
+        let mut children = Vec::new();
+        loop {
+            match read_dir.next_entry().await {
+                Ok(Some(entry)) => children.push(entry.path()),
+                Ok(None) => break,
+                Err(e) => {
+                    failed.push(failed_item(
+                        &local_path,
+                        dir_url.clone(),
+                        true,
+                        e.into(),
+                    ));
+                    break;
+                }
+            }
+        }
+        children.sort();
+
+        items.push(UploadItem {
+            local_path,
+            remote_url: dir_url.clone(),
+            is_dir: true,
+            size: 0,
+        });
+
+        queue.extend(children.into_iter().map(|c| (c, dir_url.clone())));
+    }
+
+    (items, failed)
+}
@@ -3,9 +3,54 @@ use crate::client::error::WebDavClientError;
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
 use crate::client::traits::url_trait::UrlParse;
 use async_trait::async_trait;
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 use reqwest::Url;
 use std::str::FromStr;
 
+/// 路径片段中需要转义的字符，`/`、`%`、`?`、`#` 这类在 URL 里有特殊含义的都要转
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// 对单个路径片段（文件名或目录名）做百分号编码
+/// - 本地文件名可能带有 `#`、`?`、`%`，直接拼进 URL 会被当成锚点/查询串
+pub fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// 目录路径统一补上尾部斜杠，否则 `Url::join` 会把最后一段当成文件替换掉
+pub fn as_dir_path(path: &str) -> String {
+    if path.is_empty() || path.ends_with('/') {
+        path.to_string()
+    } else {
+        format!("{path}/")
+    }
+}
+
+/// 把 href（可能是绝对路径也可能是完整 URL）解析为完整 URL
+pub(crate) fn resolve_href(
+    dir_url: &Url,
+    href: &str,
+) -> Result<Url, WebDavClientError> {
+    dir_url
+        .join(href)
+        .map_err(|e| WebDavClientError::ParseUrlErr(e.to_string()))
+}
+
 #[async_trait]
 impl UrlParse for WebDavClient {
     async fn format_url_path(
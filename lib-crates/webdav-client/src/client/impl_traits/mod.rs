@@ -2,5 +2,13 @@ pub mod impl_folder;
 pub mod impl_friendly;
 pub mod impl_url_parse;
 
+pub mod impl_capabilities;
 pub mod impl_download;
+pub mod impl_file_control;
+pub mod impl_lock;
+pub mod impl_properties;
+pub mod impl_quota;
+pub mod impl_search;
+pub mod impl_tree_walk;
+pub mod impl_upload;
 mod impl_safe_atomic_ops;
\ No newline at end of file
@@ -4,13 +4,20 @@ pub mod impl_traits;
 pub mod structs;
 pub mod traits;
 
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::provider_profile::{
+    ProviderProfile, ProviderRegistry,
+};
+use crate::client::structs::retry_policy::RetryPolicy;
+use crate::client::structs::transfer_pool::{
+    TransferLimits, TransferPool,
+};
 use crate::client::structs::webdav_child_client::{
     WebDavChildClientKey, WebDavChildClientValue,
 };
 use error::WebDavClientError;
 use std::collections::HashMap;
 use std::sync::Arc;
-use reqwest::Client;
 use tokio::sync::RwLock;
 
 pub type TWebDavChildClientValue = Arc<RwLock<WebDavChildClientValue>>;
@@ -18,11 +25,90 @@ pub type TWebDavChildClientValue = Arc<RwLock<WebDavChildClientValue>>;
 pub struct WebDavClient {
     pub(crate) clients:
         HashMap<WebDavChildClientKey, TWebDavChildClientValue>,
+    /// 上传/下载共用的传输池，所有账号的传输都受它限制
+    pub(crate) transfer_pool: TransferPool,
+    /// 服务商配置表，添加账号时按地址匹配
+    pub(crate) providers: ProviderRegistry,
+    /// 新账号默认使用的重试策略
+    pub(crate) retry_policy: RetryPolicy,
 }
 
 impl WebDavClient {
     pub fn new() -> Self {
-        Self { clients: HashMap::new() }
+        Self::with_transfer_limits(TransferLimits::default())
+    }
+
+    /// 指定传输并发上限
+    pub fn with_transfer_limits(transfer_limits: TransferLimits) -> Self {
+        Self {
+            clients: HashMap::new(),
+            transfer_pool: TransferPool::new(transfer_limits),
+            providers: ProviderRegistry::default(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// 修改传输并发上限
+    /// - 只影响之后发起的传输，正在进行的传输继续占用旧的许可
+    pub fn set_transfer_limits(
+        &mut self,
+        transfer_limits: TransferLimits,
+    ) {
+        self.transfer_pool = TransferPool::new(transfer_limits);
+    }
+
+    pub fn transfer_limits(&self) -> TransferLimits {
+        self.transfer_pool.limits()
+    }
+
+    pub fn provider_registry(&self) -> &ProviderRegistry {
+        &self.providers
+    }
+
+    /// 替换服务商配置表
+    /// - 只影响之后添加的账号，已有账号继续使用添加时匹配到的配置
+    pub fn set_provider_registry(&mut self, providers: ProviderRegistry) {
+        self.providers = providers;
+    }
+
+    /// 从 JSON 配置文件加载用户自定义的服务商配置，优先于内置配置
+    /// - 同样只影响之后添加的账号
+    pub fn load_provider_profiles(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), WebDavClientError> {
+        self.providers.load_file(path)
+    }
+
+    /// 账号匹配到的服务商配置
+    pub async fn get_provider_profile(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<Option<ProviderProfile>, WebDavClientError> {
+        let value = self.try_get_client_arc(web_dav_child_client_key)?;
+        let guard = value.read().await;
+        Ok(guard.get_profile().cloned())
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// 修改新账号默认的重试策略
+    /// - 只影响之后添加的账号，已有账号用 [`set_account_retry_policy`](Self::set_account_retry_policy)
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// 修改单个账号的重试策略
+    pub async fn set_account_retry_policy(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        retry_policy: RetryPolicy,
+    ) -> Result<(), WebDavClientError> {
+        let value = self.try_get_client_arc(web_dav_child_client_key)?;
+        value.write().await.set_retry_policy(retry_policy);
+        Ok(())
     }
 
     /// 获取http客户端实体
@@ -30,7 +116,7 @@ impl WebDavClient {
     async fn try_get_client_entity(
         &self,
         web_dav_child_client_key: &WebDavChildClientKey,
-    ) -> Result<Client, WebDavClientError> {
+    ) -> Result<WebDavHttpClient, WebDavClientError> {
         let client = match self.clients.get(web_dav_child_client_key) {
             Some(c) => Ok(c),
             None => Err(WebDavClientError::NotFindClient(
@@ -0,0 +1,366 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::digest_auth::DigestChallenge;
+use base64::Engine;
+use reqwest::header::{AUTHORIZATION, HeaderValue, WWW_AUTHENTICATE};
+use reqwest::{Client, Request, Response};
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 令牌到期前这么久就提前刷新，避免请求发出去时刚好过期
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// 账号的认证方式，在 `SafeAtomicOps::add_account_with_auth` 时指定
+#[derive(Clone)]
+pub enum AuthStrategy {
+    /// HTTP Basic，每个请求都直接带上用户名密码
+    Basic { username: String, password: String },
+    /// HTTP Digest，收到 401 质询后按质询计算，之后的请求复用同一个 nonce
+    Digest { username: String, password: String },
+    /// 固定的 Bearer 令牌
+    Bearer { token: String },
+    /// OAuth2，令牌过期或收到 401 时用 refresh_token 自动刷新
+    OAuth2(OAuth2Config),
+}
+
+impl AuthStrategy {
+    pub fn basic(username: &str, password: &str) -> Self {
+        Self::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    pub fn digest(username: &str, password: &str) -> Self {
+        Self::Digest {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    pub fn bearer(token: &str) -> Self {
+        Self::Bearer { token: token.to_string() }
+    }
+
+    pub fn oauth2(config: OAuth2Config) -> Self {
+        Self::OAuth2(config)
+    }
+
+    /// 认证方式的名字，用于日志和调试输出
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Basic { .. } => "Basic",
+            Self::Digest { .. } => "Digest",
+            Self::Bearer { .. } => "Bearer",
+            Self::OAuth2(_) => "OAuth2",
+        }
+    }
+
+    /// 用来比较两个账号是否相同的凭据（只参与哈希，不会保存明文）
+    pub(crate) fn secret(&self) -> &str {
+        match self {
+            Self::Basic { password, .. }
+            | Self::Digest { password, .. } => password,
+            Self::Bearer { token } => token,
+            Self::OAuth2(config) => &config.refresh_token,
+        }
+    }
+}
+
+/// 不输出凭据
+impl Debug for AuthStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AuthStrategy::{}", self.scheme())
+    }
+}
+
+/// OAuth2 刷新令牌所需的参数（RFC 6749 第 6 节）
+#[derive(Clone)]
+pub struct OAuth2Config {
+    /// 令牌端点
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub refresh_token: String,
+    /// 已有的访问令牌，为 `None` 时第一次请求前先刷新
+    pub access_token: Option<String>,
+    pub scope: Option<String>,
+}
+
+impl OAuth2Config {
+    pub fn new(
+        token_url: &str,
+        client_id: &str,
+        refresh_token: &str,
+    ) -> Self {
+        Self {
+            token_url: token_url.to_string(),
+            client_id: client_id.to_string(),
+            client_secret: None,
+            refresh_token: refresh_token.to_string(),
+            access_token: None,
+            scope: None,
+        }
+    }
+
+    pub fn with_client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    pub fn with_access_token(mut self, access_token: &str) -> Self {
+        self.access_token = Some(access_token.to_string());
+        self
+    }
+
+    pub fn with_scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+}
+
+/// 令牌端点的响应
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+/// OAuth2 当前持有的令牌
+struct OAuth2Token {
+    access_token: Option<String>,
+    refresh_token: String,
+    expires_at: Option<Instant>,
+}
+
+impl OAuth2Token {
+    fn valid_access_token(&self) -> Option<&str> {
+        let expired = self
+            .expires_at
+            .is_some_and(|at| Instant::now() + REFRESH_MARGIN >= at);
+        if expired {
+            return None;
+        }
+        self.access_token.as_deref()
+    }
+}
+
+enum AuthState {
+    Basic(HeaderValue),
+    Bearer(HeaderValue),
+    Digest {
+        username: String,
+        password: String,
+        challenge: Mutex<Option<DigestChallenge>>,
+    },
+    OAuth2 {
+        config: OAuth2Config,
+        /// 刷新时持有锁，并发请求同时 401 也只会刷新一次
+        token: tokio::sync::Mutex<OAuth2Token>,
+    },
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, WebDavClientError> {
+    let mut value = HeaderValue::from_str(value).map_err(|e| {
+        WebDavClientError::InvalidHeaderValue(e.to_string())
+    })?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// 按认证方式给请求加上 `Authorization`，并处理 401
+/// - clone 后共享同一份状态（Digest 的 nonce、OAuth2 的令牌）
+#[derive(Clone)]
+pub(crate) struct Authenticator {
+    state: Arc<AuthState>,
+}
+
+impl Authenticator {
+    pub fn new(strategy: AuthStrategy) -> Result<Self, WebDavClientError> {
+        let state = match strategy {
+            AuthStrategy::Basic { username, password } => {
+                let token = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{username}:{password}"));
+                AuthState::Basic(header_value(&format!("Basic {token}"))?)
+            }
+            AuthStrategy::Bearer { token } => AuthState::Bearer(
+                header_value(&format!("Bearer {token}"))?,
+            ),
+            AuthStrategy::Digest { username, password } => {
+                AuthState::Digest {
+                    username,
+                    password,
+                    challenge: Mutex::new(None),
+                }
+            }
+            AuthStrategy::OAuth2(config) => {
+                let token = OAuth2Token {
+                    access_token: config.access_token.clone(),
+                    refresh_token: config.refresh_token.clone(),
+                    expires_at: None,
+                };
+                AuthState::OAuth2 {
+                    config,
+                    token: tokio::sync::Mutex::new(token),
+                }
+            }
+        };
+
+        Ok(Self { state: Arc::new(state) })
+    }
+
+    /// Digest 还没拿到过质询时，请求体不能重发的请求需要先探测一次
+    pub fn needs_challenge(&self) -> bool {
+        match &*self.state {
+            AuthState::Digest { challenge, .. } => challenge
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_none(),
+            _ => false,
+        }
+    }
+
+    /// 给请求加上 `Authorization`，返回本次使用的凭据（OAuth2 判断是否已被别的请求刷新过）
+    pub async fn apply(
+        &self,
+        http_client: &Client,
+        request: &mut Request,
+    ) -> Result<Option<String>, WebDavClientError> {
+        let value = match &*self.state {
+            AuthState::Basic(value) | AuthState::Bearer(value) => {
+                Some(value.clone())
+            }
+            AuthState::Digest { username, password, challenge } => {
+                let mut challenge =
+                    challenge.lock().unwrap_or_else(|e| e.into_inner());
+                match challenge.as_mut() {
+                    Some(challenge) => {
+                        let url = request.url();
+                        let uri = match url.query() {
+                            Some(query) => {
+                                format!("{}?{query}", url.path())
+                            }
+                            None => url.path().to_string(),
+                        };
+                        let value = challenge.authorization(
+                            username,
+                            password,
+                            request.method().as_str(),
+                            &uri,
+                        );
+                        Some(header_value(&value)?)
+                    }
+                    // 还没有质询，先不带认证，等 401 拿到质询后重发
+                    None => None,
+                }
+            }
+            AuthState::OAuth2 { config, token } => {
+                let mut token = token.lock().await;
+                let access_token = match token.valid_access_token() {
+                    Some(access_token) => access_token.to_string(),
+                    None => {
+                        refresh_token(http_client, config, &mut token)
+                            .await?
+                    }
+                };
+                request.headers_mut().insert(
+                    AUTHORIZATION,
+                    header_value(&format!("Bearer {access_token}"))?,
+                );
+                return Ok(Some(access_token));
+            }
+        };
+
+        if let Some(value) = value {
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+        Ok(None)
+    }
+
+    /// 收到 401 后更新认证状态，返回 `true` 表示值得带上新凭据重发一次
+    /// - Basic/Bearer 凭据不会变，重发没有意义
+    /// - Digest 记录新的质询（包括 nonce 过期的 `stale=true`）
+    /// - OAuth2 刷新令牌；别的请求已经刷新过时直接重发
+    pub async fn handle_unauthorized(
+        &self,
+        http_client: &Client,
+        response: &Response,
+        used: Option<&str>,
+    ) -> Result<bool, WebDavClientError> {
+        match &*self.state {
+            AuthState::Basic(_) | AuthState::Bearer(_) => Ok(false),
+            AuthState::Digest { challenge, .. } => {
+                let parsed = response
+                    .headers()
+                    .get_all(WWW_AUTHENTICATE)
+                    .iter()
+                    .filter_map(|value| value.to_str().ok())
+                    .find_map(DigestChallenge::parse);
+
+                match parsed {
+                    Some(parsed) => {
+                        *challenge
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner()) =
+                            Some(parsed);
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            AuthState::OAuth2 { config, token } => {
+                let mut token = token.lock().await;
+                if token.access_token.as_deref() != used {
+                    return Ok(true);
+                }
+                refresh_token(http_client, config, &mut token).await?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// 用 refresh_token 换新的访问令牌
+/// - 令牌端点返回新的 refresh_token 时一并替换
+async fn refresh_token(
+    http_client: &Client,
+    config: &OAuth2Config,
+    token: &mut OAuth2Token,
+) -> Result<String, WebDavClientError> {
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", token.refresh_token.as_str()),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if let Some(client_secret) = &config.client_secret {
+        form.push(("client_secret", client_secret));
+    }
+    if let Some(scope) = &config.scope {
+        form.push(("scope", scope));
+    }
+
+    let res =
+        http_client.post(&config.token_url).form(&form).send().await?;
+
+    let status = res.status();
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(WebDavClientError::AuthErr(format!(
+            "刷新令牌失败 {status}: {text}"
+        )));
+    }
+
+    let TokenResponse { access_token, expires_in, refresh_token } =
+        res.json().await?;
+
+    token.access_token = Some(access_token.clone());
+    token.expires_at =
+        expires_in.map(|secs| Instant::now() + Duration::from_secs(secs));
+    if let Some(refresh_token) = refresh_token {
+        token.refresh_token = refresh_token;
+    }
+
+    Ok(access_token)
+}
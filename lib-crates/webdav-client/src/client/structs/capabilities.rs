@@ -0,0 +1,94 @@
+use reqwest::header::{ACCEPT_RANGES, ALLOW, HeaderMap};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 账号所在服务端的能力，添加账号时探测一次后缓存在账号上
+/// - 探测不到的项保持保守的默认值（不支持/未知）
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ServerCapabilities {
+    /// `DAV` 响应头里的合规等级，例如 `1`、`2`、`3`、`access-control`
+    pub dav_classes: Vec<String>,
+    /// `Allow` 响应头里允许的方法（大写）
+    pub allowed_methods: Vec<String>,
+    /// 是否支持 Range 请求
+    /// - `Some(true)`/`Some(false)` 为探测结果，`None` 表示无法判断
+    pub accept_ranges: Option<bool>,
+    /// `DASL` 响应头是否声明了 `DAV:basicsearch`（RFC 5323）
+    pub supports_search: bool,
+    /// 根目录是否返回 RFC 4331 配额属性（`quota-available-bytes`/`quota-used-bytes`）
+    pub supports_quota: bool,
+    /// 是否接受 `Depth: infinity` 的 PROPFIND
+    /// - 探测时不发这种请求，第一次遍历目录树时试过后记下结果，`None` 表示还没试过
+    pub depth_infinity: Option<bool>,
+}
+
+/// 账号上缓存的探测结果，探测期间持有锁，并发调用只会探测一次
+pub type TCapabilitiesCache = Arc<Mutex<Option<ServerCapabilities>>>;
+
+/// 把可能重复出现、逗号分隔的响应头拆成列表
+fn header_list(headers: &HeaderMap, name: &str) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+impl ServerCapabilities {
+    /// 从 `OPTIONS` 响应头读取合规等级、允许的方法和搜索支持
+    pub fn from_options_headers(headers: &HeaderMap) -> Self {
+        let accept_ranges = headers
+            .get(ACCEPT_RANGES)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().eq_ignore_ascii_case("bytes"));
+
+        Self {
+            dav_classes: header_list(headers, "DAV"),
+            allowed_methods: header_list(headers, ALLOW.as_str())
+                .into_iter()
+                .map(|method| method.to_ascii_uppercase())
+                .collect(),
+            accept_ranges,
+            supports_search: header_list(headers, "DASL").iter().any(
+                |grammar| {
+                    grammar.to_ascii_lowercase().contains("basicsearch")
+                },
+            ),
+            supports_quota: false,
+            depth_infinity: None,
+        }
+    }
+
+    /// 是否声明了某个合规等级
+    pub fn supports_class(&self, class: &str) -> bool {
+        self.dav_classes.iter().any(|c| c.eq_ignore_ascii_case(class))
+    }
+
+    /// 是否允许某个方法；服务端没给 `Allow` 时无法判断，按允许处理
+    pub fn allows(&self, method: &str) -> bool {
+        self.allowed_methods.is_empty()
+            || self
+                .allowed_methods
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(method))
+    }
+
+    /// 是否可以按 Range 分片下载；未知时按支持处理，单个文件收到 200 时下载会自动退回整文件
+    pub fn supports_ranges(&self) -> bool {
+        self.accept_ranges != Some(false)
+    }
+
+    /// 遍历目录树时是否先试 `Depth: infinity`；没试过时按允许处理
+    pub fn allows_depth_infinity(&self) -> bool {
+        self.depth_infinity != Some(false)
+    }
+
+    /// 是否支持 LOCK/UNLOCK（合规等级 2）
+    pub fn supports_locking(&self) -> bool {
+        self.supports_class("2")
+    }
+}
@@ -0,0 +1,130 @@
+use md5::Md5;
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+
+/// 支持校验的哈希算法，越靠后越可靠
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+)]
+pub enum ChecksumAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// 按服务端给出的算法名识别（大小写不敏感），不认识的返回 `None`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "SHA1" | "SHA-1" => Some(Self::Sha1),
+            "SHA256" | "SHA-256" => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    /// 哈希值的十六进制长度，用来识别没有带算法名的值
+    fn hex_len(self) -> usize {
+        match self {
+            Self::Md5 => 32,
+            Self::Sha1 => 40,
+            Self::Sha256 => 64,
+        }
+    }
+}
+
+impl Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Md5 => write!(f, "MD5"),
+            Self::Sha1 => write!(f, "SHA1"),
+            Self::Sha256 => write!(f, "SHA256"),
+        }
+    }
+}
+
+/// 服务端给出的文件校验和，`value` 统一为小写十六进制
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Checksum {
+    pub algorithm: ChecksumAlgorithm,
+    pub value: String,
+}
+
+impl Checksum {
+    pub fn new(algorithm: ChecksumAlgorithm, value: &str) -> Option<Self> {
+        let value = value.trim().trim_matches('"').to_ascii_lowercase();
+        let valid = value.len() == algorithm.hex_len()
+            && value.chars().all(|c| c.is_ascii_hexdigit());
+        valid.then_some(Self { algorithm, value })
+    }
+
+    /// 解析 `算法:值` 列表，例如 Nextcloud 的 `oc:checksums` 和 `OC-Checksum` 头：
+    /// `SHA1:abc... MD5:def... ADLER32:123`
+    /// - 以空白或逗号分隔，不认识的算法和格式不对的值直接忽略
+    pub fn parse_list(raw: &str) -> Vec<Self> {
+        raw.split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|item| {
+                let (name, value) = item.split_once(':')?;
+                Self::new(ChecksumAlgorithm::from_name(name)?, value)
+            })
+            .collect()
+    }
+
+    /// 从服务商自定义的单值属性（只有哈希值，没有算法名）解析
+    pub fn from_bare(
+        algorithm: ChecksumAlgorithm,
+        raw: &str,
+    ) -> Option<Self> {
+        // 有的服务商会带上算法前缀，有的不会
+        let value = raw.rsplit(':').next().unwrap_or(raw);
+        Self::new(algorithm, value)
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.value)
+    }
+}
+
+/// 选出最可靠的一个校验和，同一文件只需要算一次
+pub fn strongest(checksums: &[Checksum]) -> Option<&Checksum> {
+    checksums.iter().max_by_key(|checksum| checksum.algorithm)
+}
+
+/// 增量计算文件哈希，数据可以分多次喂进来
+pub enum ChecksumHasher {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl ChecksumHasher {
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Md5 => Self::Md5(Md5::new()),
+            ChecksumAlgorithm::Sha1 => Self::Sha1(Sha1::new()),
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Md5(hasher) => hasher.update(bytes),
+            Self::Sha1(hasher) => hasher.update(bytes),
+            Self::Sha256(hasher) => hasher.update(bytes),
+        }
+    }
+
+    /// 返回小写十六进制的哈希值
+    pub fn finalize(self) -> String {
+        let digest = match self {
+            Self::Md5(hasher) => hasher.finalize().to_vec(),
+            Self::Sha1(hasher) => hasher.finalize().to_vec(),
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+        };
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
@@ -0,0 +1,244 @@
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 服务端在 `WWW-Authenticate: Digest ...` 里给出的质询（RFC 7616）
+#[derive(Clone, Debug)]
+pub struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    /// 服务端支持 `qop=auth` 时为 `true`，否则按 RFC 2069 的旧格式计算
+    qop_auth: bool,
+    /// 同一个 nonce 下已经用过的次数
+    nonce_count: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "MD5-SESS" => Some(Self::Md5Sess),
+            "SHA-256" => Some(Self::Sha256),
+            "SHA-256-SESS" => Some(Self::Sha256Sess),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Md5Sess => "MD5-sess",
+            Self::Sha256 => "SHA-256",
+            Self::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn is_sess(self) -> bool {
+        matches!(self, Self::Md5Sess | Self::Sha256Sess)
+    }
+
+    fn hash(self, data: &str) -> String {
+        let digest = match self {
+            Self::Md5 | Self::Md5Sess => Md5::digest(data).to_vec(),
+            Self::Sha256 | Self::Sha256Sess => {
+                Sha256::digest(data).to_vec()
+            }
+        };
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+/// 拆分认证参数 `key=value, key="quoted, value"`，引号里的逗号不拆
+fn split_params(params: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut chars = params.chars().peekable();
+
+    loop {
+        // 跳过分隔符
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {
+        }
+
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if key.is_empty() || chars.next().is_none() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| {
+                chars.next_if(|c| *c != ',')
+            }));
+        }
+
+        result.push((
+            key.trim().to_ascii_lowercase(),
+            value.trim().to_string(),
+        ));
+    }
+
+    result
+}
+
+/// 把 `WWW-Authenticate` 拆成 `(方案, 参数)`，引号里的内容不参与拆分
+/// - 开头或逗号之后的 token 后面不是 `=` 时是新方案的名字，否则是上一个方案的参数
+fn split_challenges(header: &str) -> Vec<(&str, &str)> {
+    let bytes = header.as_bytes();
+    // 每个方案名的起止位置
+    let mut schemes = Vec::new();
+    let mut in_quotes = false;
+    let mut at_item_start = true;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_quotes => i += 1,
+            b'"' => {
+                in_quotes = !in_quotes;
+                at_item_start = false;
+            }
+            _ if in_quotes => {}
+            b',' => at_item_start = true,
+            b' ' | b'\t' => {}
+            _ if at_item_start => {
+                at_item_start = false;
+                let end = header[i..]
+                    .find([' ', '\t', ',', '='])
+                    .map_or(header.len(), |n| i + n);
+                if !header[end..].trim_start().starts_with('=') {
+                    schemes.push((i, end));
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    schemes
+        .iter()
+        .enumerate()
+        .map(|(n, &(start, end))| {
+            let params_end = schemes
+                .get(n + 1)
+                .map_or(header.len(), |&(next_start, _)| next_start);
+            (&header[start..end], &header[end..params_end])
+        })
+        .collect()
+}
+
+impl DigestChallenge {
+    /// 从一个 `WWW-Authenticate` 头解析 Digest 质询，不是 Digest 或缺少必要参数时返回 `None`
+    /// - 同一个头里可能并列多个方案（如 `Basic realm=.., Digest ..`），按方案拆开后只取 Digest 的参数
+    pub fn parse(header: &str) -> Option<Self> {
+        let (_, params) = split_challenges(header)
+            .into_iter()
+            .find(|(scheme, _)| scheme.eq_ignore_ascii_case("digest"))?;
+        let params = split_params(params);
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let algorithm = match get("algorithm") {
+            Some(name) => DigestAlgorithm::from_name(&name)?,
+            None => DigestAlgorithm::Md5,
+        };
+        let qop_auth = get("qop").is_some_and(|qop| {
+            qop.split(',').any(|q| q.trim().eq_ignore_ascii_case("auth"))
+        });
+
+        Some(Self {
+            realm: get("realm").unwrap_or_default(),
+            nonce: get("nonce")?,
+            opaque: get("opaque"),
+            algorithm,
+            qop_auth,
+            nonce_count: 0,
+        })
+    }
+
+    /// 按本次请求计算 `Authorization` 的值，每调用一次 nonce 计数加一
+    pub fn authorization(
+        &mut self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+    ) -> String {
+        self.nonce_count += 1;
+
+        let algorithm = self.algorithm;
+        let nc = format!("{:08x}", self.nonce_count);
+        let cnonce = new_cnonce();
+
+        let mut ha1 = algorithm
+            .hash(&format!("{username}:{}:{password}", self.realm));
+        if algorithm.is_sess() {
+            ha1 =
+                algorithm.hash(&format!("{ha1}:{}:{cnonce}", self.nonce));
+        }
+        let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+
+        let response = if self.qop_auth {
+            algorithm.hash(&format!(
+                "{ha1}:{}:{nc}:{cnonce}:auth:{ha2}",
+                self.nonce
+            ))
+        } else {
+            algorithm.hash(&format!("{ha1}:{}:{ha2}", self.nonce))
+        };
+
+        let mut header = format!(
+            r#"Digest username="{username}", realm="{}", nonce="{}", uri="{uri}", algorithm={}, response="{response}""#,
+            self.realm,
+            self.nonce,
+            algorithm.name()
+        );
+        if self.qop_auth {
+            header.push_str(&format!(
+                r#", qop=auth, nc={nc}, cnonce="{cnonce}""#
+            ));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(r#", opaque="{opaque}""#));
+        }
+        header
+    }
+}
+
+/// 客户端随机数，只要求每次不同，不需要密码学强度
+fn new_cnonce() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    DigestAlgorithm::Sha256.hash(&format!("{nanos}:{count}"))[..16]
+        .to_string()
+}
@@ -1,6 +1,8 @@
 use crate::client::error::WebDavClientError;
+use crate::client::structs::checksum::{Checksum, ChecksumAlgorithm};
 use crate::client::structs::raw_xml::{
-    CurrentUserPrivilegeSet, MultiStatus, Prop, PropStat, Response,
+    Checksums, CurrentUserPrivilegeSet, MultiStatus, Prop, PropStat,
+    Response,
 };
 use chrono::{DateTime, FixedOffset};
 
@@ -12,13 +14,15 @@ pub struct FriendlyResource {
     pub size: Option<u64>, // 文件大小（字节）
     pub size_str: Option<String>, // 格式化后的大小，比如 "12.3MB"
     pub last_modified: Option<DateTime<FixedOffset>>, // 原始时间
+    pub creation_date: Option<DateTime<FixedOffset>>, // 创建时间
     pub mime: Option<String>, // MIME 类型
     pub owner: Option<String>, // 所有者
     pub etag: Option<String>, // 清理后的 ETag
     pub privileges: Vec<String>, // 权限列表
+    pub checksums: Vec<Checksum>, // 服务端给出的校验和，下载后用来校验
 }
 
-fn format_size(len: Option<u64>) -> Option<String> {
+pub(crate) fn format_size(len: Option<u64>) -> Option<String> {
     // 将字节数转换为友好化的字符串表示
     len.map(|len| {
         if len < 1024 {
@@ -92,6 +96,38 @@ fn clean_etag(raw: Option<String>) -> Option<String> {
     raw.map(|s| s.trim().trim_matches('"').to_string())
 }
 
+fn collect_checksums(
+    checksums: Option<Checksums>,
+    md5: Option<String>,
+    sha1: Option<String>,
+) -> Vec<Checksum> {
+    // 合并 oc:checksums 和服务商的单值属性，同一算法只保留第一个
+    let mut result: Vec<Checksum> = checksums
+        .map(|c| c.checksums)
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|raw| Checksum::parse_list(raw))
+        .collect();
+
+    let bare =
+        [(ChecksumAlgorithm::Md5, md5), (ChecksumAlgorithm::Sha1, sha1)];
+    for (algorithm, raw) in bare {
+        if let Some(checksum) =
+            raw.and_then(|raw| Checksum::from_bare(algorithm, &raw))
+        {
+            result.push(checksum);
+        }
+    }
+
+    let mut seen = Vec::new();
+    result.retain(|c| {
+        let first = !seen.contains(&c.algorithm);
+        seen.push(c.algorithm);
+        first
+    });
+    result
+}
+
 impl FriendlyResource {
     /// 从 MultiStatus 构造资源列表
     ///
@@ -101,56 +137,65 @@ impl FriendlyResource {
     pub fn new(
         multi_status: MultiStatus,
     ) -> Result<Vec<Self>, WebDavClientError> {
-        let mut resources = Vec::new();
-
-        // 消耗 multi_status.responses 中的每个 Response
-        for Response { href, propstats } in multi_status.responses {
-            // 挑选出第一个 2xx PropStat（消耗 propstats 避免 clone）
-            let ok_ps = match take_ok_propstat(propstats) {
-                Some(ps) => ps,
-                None => continue, // 没有 2xx 状态就跳过
-            };
-
-            // 解构 PropStat，move 出 prop
-            let PropStat { prop, .. } = ok_ps;
-
-            // 再解构 Prop，move 出需要的字段
-            let Prop {
-                resource_type,
-                content_length: size,
-                last_modified,
-                content_type: mime,
-                display_name,
-                owner,
-                etag,
-                current_user_privilege_set,
-                ..
-            } = prop;
-
-            // 提前计算 name（因为等下 href 要被 move 进结构体）
-            let name = decode_name(display_name, &href);
-
-            // 判断是否目录
-            let is_dir = resource_type
-                .as_ref()
-                .and_then(|rt| rt.is_collection.as_ref())
-                .is_some();
-
-            // 构造最终 FriendlyResource，绝大部分字段直接 move
-            resources.push(FriendlyResource {
-                full_path: href, // move
-                name,            // 已提前生成
-                is_dir,
-                size,
-                size_str: format_size(size),
-                last_modified, // move
-                mime,          // move
-                owner,         // move
-                etag: clean_etag(etag),
-                privileges: extract_privileges(current_user_privilege_set),
-            });
-        }
+        // 消耗 multi_status.responses 中的每个 Response，没有 2xx 状态的跳过
+        Ok(multi_status
+            .responses
+            .into_iter()
+            .filter_map(Self::from_response)
+            .collect())
+    }
+
+    /// 从单个 Response 构造资源，没有 2xx 的 PropStat 时返回 `None`
+    /// - 流式解析时逐个转换，不需要先攒成 MultiStatus
+    pub fn from_response(response: Response) -> Option<Self> {
+        let Response { href, propstats } = response;
+
+        // 挑选出第一个 2xx PropStat（消耗 propstats 避免 clone）
+        let ok_ps = take_ok_propstat(propstats)?;
+
+        // 解构 PropStat，move 出 prop
+        let PropStat { prop, .. } = ok_ps;
+
+        // 再解构 Prop，move 出需要的字段
+        let Prop {
+            resource_type,
+            content_length: size,
+            last_modified,
+            creation_date,
+            content_type: mime,
+            display_name,
+            owner,
+            etag,
+            current_user_privilege_set,
+            checksums,
+            md5,
+            sha1,
+            ..
+        } = prop;
+
+        // 提前计算 name（因为等下 href 要被 move 进结构体）
+        let name = decode_name(display_name, &href);
+
+        // 判断是否目录
+        let is_dir = resource_type
+            .as_ref()
+            .and_then(|rt| rt.is_collection.as_ref())
+            .is_some();
 
-        Ok(resources)
+        // 构造最终 FriendlyResource，绝大部分字段直接 move
+        Some(FriendlyResource {
+            full_path: href, // move
+            name,            // 已提前生成
+            is_dir,
+            size,
+            size_str: format_size(size),
+            last_modified, // move
+            creation_date, // move
+            mime,          // move
+            owner,         // move
+            etag: clean_etag(etag),
+            privileges: extract_privileges(current_user_privilege_set),
+            checksums: collect_checksums(checksums, md5, sha1),
+        })
     }
 }
@@ -0,0 +1,228 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::auth::{AuthStrategy, Authenticator};
+use crate::client::structs::lock::LockStore;
+use crate::client::structs::provider_profile::ProviderProfile;
+use crate::client::structs::rate_limiter::RateLimiter;
+use crate::client::structs::retry_policy::RetryPolicy;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{
+    Client, Method, Request, RequestBuilder, Response, StatusCode,
+};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// 账号专用的 HTTP 客户端：`reqwest::Client` + 认证方式 + 服务商配置
+/// - `get`/`put`/`request` 只负责组装请求，必须经过 [`send`](Self::send) 发送才会带上认证
+/// - clone 开销很小，内部都是 `Arc`
+#[derive(Clone)]
+pub struct WebDavHttpClient {
+    client: Client,
+    authenticator: Authenticator,
+    profile: Option<Arc<ProviderProfile>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_policy: Arc<RetryPolicy>,
+    /// 账号持有的锁，写请求自动带上对应的 `If` 头
+    pub(crate) locks: LockStore,
+}
+
+const IF: HeaderName = HeaderName::from_static("if");
+const DESTINATION: HeaderName = HeaderName::from_static("destination");
+
+/// 会修改远端资源、需要提交锁令牌的方法
+const WRITE_METHODS: [&str; 8] = [
+    "PUT",
+    "POST",
+    "PATCH",
+    "DELETE",
+    "MKCOL",
+    "MOVE",
+    "COPY",
+    "PROPPATCH",
+];
+
+impl WebDavHttpClient {
+    pub(crate) fn new(
+        auth: AuthStrategy,
+        profile: Option<ProviderProfile>,
+    ) -> Result<Self, WebDavClientError> {
+        let client = Client::builder().http1_only().build()?;
+
+        let rate_limiter = profile
+            .as_ref()
+            .and_then(|profile| profile.rate_limit)
+            .map(|rate_limit| Arc::new(RateLimiter::new(rate_limit)));
+
+        Ok(Self {
+            client,
+            authenticator: Authenticator::new(auth)?,
+            profile: profile.map(Arc::new),
+            rate_limiter,
+            retry_policy: Arc::new(RetryPolicy::default()),
+            locks: LockStore::default(),
+        })
+    }
+
+    /// 账号匹配到的服务商配置
+    pub fn profile(&self) -> Option<&ProviderProfile> {
+        self.profile.as_deref()
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// 换一个重试策略，只影响之后从账号取出的客户端
+    pub(crate) fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = Arc::new(retry_policy);
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        self.client.put(url)
+    }
+
+    pub fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// 写请求涉及账号持有的锁时加上 `If` 头，调用方已经自己带了 `If` 的不动
+    fn apply_lock_tokens(
+        &self,
+        request: &mut Request,
+    ) -> Result<(), WebDavClientError> {
+        if request.headers().contains_key(IF)
+            || !WRITE_METHODS.contains(&request.method().as_str())
+        {
+            return Ok(());
+        }
+
+        let destination = request
+            .headers()
+            .get(DESTINATION)
+            .and_then(|value| value.to_str().ok());
+
+        if let Some(value) =
+            self.locks.if_header(request.url().as_str(), destination)
+        {
+            let value = HeaderValue::from_str(&value).map_err(|e| {
+                WebDavClientError::InvalidHeaderValue(e.to_string())
+            })?;
+            request.headers_mut().insert(IF, value);
+        }
+
+        Ok(())
+    }
+
+    /// 发出请求，服务商有频率上限时先排队
+    async fn execute(
+        &self,
+        request: Request,
+    ) -> Result<Response, WebDavClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        Ok(self.client.execute(request).await?)
+    }
+
+    /// 带上认证发送请求，失败时按账号的 [`RetryPolicy`] 重试
+    /// - 写请求自动带上账号持有的锁令牌
+    pub async fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, WebDavClientError> {
+        let mut request = request.build()?;
+        self.apply_lock_tokens(&mut request)?;
+        let method = request.method().clone();
+
+        let mut attempt = 1;
+        loop {
+            let next = (attempt < self.retry_policy.max_attempts)
+                .then(|| request.try_clone())
+                .flatten();
+
+            let result = self.send_once(request).await;
+
+            let Some(next) = next else {
+                return result;
+            };
+
+            let delay = match &result {
+                Ok(response) => self
+                    .retry_policy
+                    .delay_for_response(&method, response, attempt),
+                Err(e) => {
+                    self.retry_policy.delay_for_error(&method, e, attempt)
+                }
+            };
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            drop(result);
+            tokio::time::sleep(delay).await;
+            request = next;
+            attempt += 1;
+        }
+    }
+
+    /// 带上认证发送一次
+    /// - 收到 401 且认证方式能更新凭据（Digest 质询、OAuth2 刷新）时重发一次
+    /// - 流式请求体不能重发，Digest 还没拿到质询时先用 `OPTIONS` 探测一次
+    async fn send_once(
+        &self,
+        mut request: Request,
+    ) -> Result<Response, WebDavClientError> {
+        let retry = request.try_clone();
+
+        if retry.is_none() && self.authenticator.needs_challenge() {
+            let probe = self
+                .execute(
+                    self.client
+                        .request(Method::OPTIONS, request.url().clone())
+                        .build()?,
+                )
+                .await?;
+            if probe.status() == StatusCode::UNAUTHORIZED {
+                self.authenticator
+                    .handle_unauthorized(&self.client, &probe, None)
+                    .await?;
+            }
+        }
+
+        let used =
+            self.authenticator.apply(&self.client, &mut request).await?;
+        let response = self.execute(request).await?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let Some(mut retry) = retry else {
+            return Ok(response);
+        };
+
+        let renewed = self
+            .authenticator
+            .handle_unauthorized(&self.client, &response, used.as_deref())
+            .await?;
+        if !renewed {
+            return Ok(response);
+        }
+
+        self.authenticator.apply(&self.client, &mut retry).await?;
+        self.execute(retry).await
+    }
+}
+
+/// 不输出认证状态
+impl Debug for WebDavHttpClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebDavHttpClient")
+            .field("client", &self.client)
+            .field("profile", &self.profile)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+
+/// 带时区偏移的格式
+const OFFSET_FORMATS: &[&str] = &[
+    // ISO 8601，偏移不带冒号，如 `2024-05-01T08:00:00+0800`
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+];
+
+/// 不带时区偏移的格式，一律按 UTC 处理
+const NAIVE_FORMATS: &[&str] = &[
+    // RFC 850，如 `Sunday, 06-Nov-94 08:49:37 GMT`
+    "%A, %d-%b-%y %H:%M:%S GMT",
+    // asctime，如 `Sun Nov  6 08:49:37 1994`
+    "%a %b %e %H:%M:%S %Y",
+    // RFC 1123 但写成 UTC，如 `Sun, 06 Nov 1994 08:49:37 UTC`
+    "%a, %d %b %Y %H:%M:%S UTC",
+    // ISO 8601 不带时区
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+
+/// 宽松地解析服务端返回的时间，`getlastmodified`、`creationdate`、`Retry-After` 共用
+/// - 支持 RFC 1123（RFC 2822）、RFC 850、asctime 和 ISO 8601（RFC 3339）
+/// - 无法识别时返回 `None`
+pub fn parse_http_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc2822(value)
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+    {
+        return Some(date);
+    }
+
+    OFFSET_FORMATS
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NAIVE_FORMATS.iter().find_map(|format| {
+                NaiveDateTime::parse_from_str(value, format)
+                    .ok()
+                    .map(|date| date.and_utc().fixed_offset())
+            })
+        })
+}
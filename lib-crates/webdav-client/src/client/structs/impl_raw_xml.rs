@@ -1,2 +1,435 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::http_date::parse_http_date;
+use crate::client::structs::propfind::{
+    DAV_NAMESPACE, OC_NAMESPACE, PropName,
+};
+use crate::client::structs::raw_xml::{
+    ActiveLock, Checksums, CurrentUserPrivilegeSet, EmptyElement,
+    HrefElement, LockDiscovery, LockOwner, LockScopeElement, MultiStatus,
+    Privilege, Prop, PropStat, ResourceType, Response,
+};
+use chrono::{DateTime, FixedOffset};
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::reader::NsReader;
 
+fn xml_err(e: impl ToString) -> WebDavClientError {
+    WebDavClientError::SerdeErr(e.to_string())
+}
 
+fn resolved_namespace(resolved: ResolveResult) -> String {
+    match resolved {
+        ResolveResult::Bound(namespace) => {
+            String::from_utf8_lossy(namespace.as_ref()).to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+fn local_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).to_string()
+}
+
+/// 属性值：纯文本反转义，带子元素时保留原始 XML
+fn prop_value(raw: &str) -> String {
+    if raw.contains('<') {
+        return raw.trim().to_string();
+    }
+    unescape(raw)
+        .map(|text| text.trim().to_string())
+        .unwrap_or_else(|_| raw.trim().to_string())
+}
+
+/// 状态行 `HTTP/1.1 200 OK` 里的状态码
+pub(crate) fn status_code(status: &str) -> Option<u16> {
+    status.split_whitespace().find_map(|token| token.parse::<u16>().ok())
+}
+
+/// 解析后的元素，命名空间已按前缀声明（包括默认命名空间）解析
+struct Element {
+    /// 没有命名空间时为空字符串
+    namespace: String,
+    name: String,
+    /// 元素内的原始 XML，空元素为空字符串
+    raw: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    /// 是否为 `DAV:` 下名为 `name` 的元素
+    /// - 有的服务端完全不声明命名空间，没有命名空间的元素也按 `DAV:` 处理
+    fn is_dav(&self, name: &str) -> bool {
+        self.name == name
+            && (self.namespace == DAV_NAMESPACE
+                || self.namespace.is_empty())
+    }
+
+    fn dav_children<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.is_dav(name))
+    }
+
+    fn dav_child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.is_dav(name))
+    }
+
+    fn has_dav_child(&self, name: &str) -> Option<EmptyElement> {
+        self.dav_child(name).map(|_| EmptyElement {})
+    }
+
+    /// 文本内容：没有子元素时取自身的文本，否则取第一个有文本的子元素
+    /// - 例如 `<owner><href>mailto:a@b.c</href></owner>` 取到 `mailto:a@b.c`
+    fn text(&self) -> String {
+        if self.children.is_empty() {
+            let raw = self.raw.trim();
+            return match raw
+                .strip_prefix("<![CDATA[")
+                .and_then(|raw| raw.strip_suffix("]]>"))
+            {
+                Some(cdata) => cdata.trim().to_string(),
+                None => prop_value(raw),
+            };
+        }
+        self.children
+            .iter()
+            .map(Element::text)
+            .find(|text| !text.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// 文本内容，为空时返回 `None`
+    fn non_empty_text(&self) -> Option<String> {
+        Some(self.text()).filter(|text| !text.is_empty())
+    }
+
+    fn prop_name(&self) -> PropName {
+        PropName {
+            namespace: self.namespace.clone(),
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// 把整个文档解析成元素树，返回一个不对应任何元素的根节点，文档的根元素是它的子节点
+fn parse_document(xml: &str) -> Result<Element, WebDavClientError> {
+    let mut reader = NsReader::from_str(xml);
+    // (元素, 元素内容在 xml 里的起始位置)
+    let mut stack = vec![(
+        Element {
+            namespace: String::new(),
+            name: String::new(),
+            raw: String::new(),
+            children: Vec::new(),
+        },
+        0,
+    )];
+
+    loop {
+        let before = reader.buffer_position() as usize;
+        let (resolved, event) =
+            reader.read_resolved_event().map_err(xml_err)?;
+        // 先转成 String，释放对 reader 的借用
+        let namespace = resolved_namespace(resolved);
+
+        match event {
+            Event::Start(e) => {
+                let element = Element {
+                    namespace,
+                    name: local_name(&e),
+                    raw: String::new(),
+                    children: Vec::new(),
+                };
+                stack.push((element, reader.buffer_position() as usize));
+            }
+            Event::Empty(e) => {
+                if let Some((parent, _)) = stack.last_mut() {
+                    parent.children.push(Element {
+                        namespace,
+                        name: local_name(&e),
+                        raw: String::new(),
+                        children: Vec::new(),
+                    });
+                }
+            }
+            Event::End(_) => {
+                // 结束标签不匹配时 reader 已经报错，这里栈里至少还有根节点
+                if stack.len() > 1
+                    && let Some((mut element, start)) = stack.pop()
+                {
+                    element.raw = xml[start..before].to_string();
+                    if let Some((parent, _)) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(WebDavClientError::SerdeErr(
+            "XML 不完整，有元素没有结束".to_string(),
+        ));
+    }
+    Ok(stack.remove(0).0)
+}
+
+/// 文档里所有的 `<DAV:response>`
+fn dav_responses(
+    document: &Element,
+) -> Result<Vec<&Element>, WebDavClientError> {
+    let multi_status =
+        document.dav_child("multistatus").ok_or_else(|| {
+            WebDavClientError::SerdeErr(
+                "响应体不是 multistatus".to_string(),
+            )
+        })?;
+    Ok(multi_status.dav_children("response").collect())
+}
+
+/// 解析时间属性，格式无法识别时记一条警告并按缺失处理，不影响整个列表
+fn read_date(element: &Element) -> Option<DateTime<FixedOffset>> {
+    let text = element.non_empty_text()?;
+    let date = parse_http_date(&text);
+    if date.is_none() {
+        tracing::warn!(
+            "无法解析 <{}> 的时间 {:?}，按缺失处理",
+            element.name,
+            text
+        );
+    }
+    date
+}
+
+/// 按命名空间和本地名填充 `Prop` 的字段，不是已知属性时返回 `false`
+/// - `DAV:` 的属性必须在 `DAV:` 命名空间下，其他命名空间的同名属性进入 `dead_props`
+/// - `oc:checksums` 必须在 ownCloud 命名空间下；`md5`/`sha1` 各服务商的命名空间不同，不限制
+fn read_known_prop(prop: &mut Prop, element: &Element) -> bool {
+    let is_dav =
+        element.namespace == DAV_NAMESPACE || element.namespace.is_empty();
+
+    match (is_dav, element.name.as_str()) {
+        (true, "resourcetype") => {
+            prop.resource_type = Some(ResourceType {
+                is_collection: element.has_dav_child("collection"),
+            })
+        }
+        (true, "getcontentlength") => {
+            prop.content_length = element.text().parse().ok()
+        }
+        (true, "getlastmodified") => {
+            prop.last_modified = read_date(element)
+        }
+        (true, "getcontenttype") => {
+            prop.content_type = element.non_empty_text()
+        }
+        (true, "creationdate") => prop.creation_date = read_date(element),
+        (true, "getetag") => prop.etag = element.non_empty_text(),
+        (true, "displayname") => {
+            prop.display_name = element.non_empty_text()
+        }
+        (true, "owner") => prop.owner = element.non_empty_text(),
+        (true, "current-user-privilege-set") => {
+            prop.current_user_privilege_set =
+                Some(CurrentUserPrivilegeSet {
+                    privileges: element
+                        .dav_children("privilege")
+                        .map(parse_privilege)
+                        .collect(),
+                })
+        }
+        (true, "quota-available-bytes") => {
+            prop.quota_available_bytes = element.non_empty_text()
+        }
+        (true, "quota-used-bytes") => {
+            prop.quota_used_bytes = element.non_empty_text()
+        }
+        (true, "lockdiscovery") => {
+            prop.lock_discovery = Some(LockDiscovery {
+                active_locks: element
+                    .dav_children("activelock")
+                    .map(parse_active_lock)
+                    .collect(),
+            })
+        }
+        (false, "checksums") if element.namespace == OC_NAMESPACE => {
+            prop.checksums = Some(Checksums {
+                checksums: element
+                    .children
+                    .iter()
+                    .filter(|child| child.name == "checksum")
+                    .map(Element::text)
+                    .collect(),
+            })
+        }
+        (false, "md5") => prop.md5 = element.non_empty_text(),
+        (false, "sha1") => prop.sha1 = element.non_empty_text(),
+        _ => return false,
+    }
+    true
+}
+
+fn parse_privilege(element: &Element) -> Privilege {
+    Privilege {
+        read: element.has_dav_child("read"),
+        write: element.has_dav_child("write"),
+        all: element.has_dav_child("all"),
+        read_acl: element.has_dav_child("read-acl"),
+        write_acl: element.has_dav_child("write-acl"),
+    }
+}
+
+fn parse_href_element(element: &Element) -> Option<HrefElement> {
+    element.dav_child("href").map(|href| HrefElement { href: href.text() })
+}
+
+fn parse_active_lock(element: &Element) -> ActiveLock {
+    ActiveLock {
+        lock_scope: element.dav_child("lockscope").map(|scope| {
+            LockScopeElement {
+                exclusive: scope.has_dav_child("exclusive"),
+                shared: scope.has_dav_child("shared"),
+            }
+        }),
+        depth: element.dav_child("depth").map(Element::text),
+        owner: element.dav_child("owner").map(|owner| LockOwner {
+            href: owner.dav_child("href").map(Element::text),
+            text: owner
+                .children
+                .is_empty()
+                .then(|| owner.text())
+                .filter(|text| !text.is_empty()),
+        }),
+        timeout: element.dav_child("timeout").map(Element::text),
+        lock_token: element
+            .dav_child("locktoken")
+            .and_then(parse_href_element),
+        lock_root: element
+            .dav_child("lockroot")
+            .and_then(parse_href_element),
+    }
+}
+
+/// `<prop>` 的每个子元素：已知属性填进字段，其余按命名空间和本地名放进 `dead_props`
+fn parse_prop(element: &Element) -> Prop {
+    let mut prop = Prop::default();
+    for child in &element.children {
+        if !read_known_prop(&mut prop, child) {
+            prop.dead_props
+                .insert(child.prop_name(), prop_value(&child.raw));
+        }
+    }
+    prop
+}
+
+fn parse_response(element: &Element) -> Response {
+    Response {
+        href: element
+            .dav_child("href")
+            .map(Element::text)
+            .unwrap_or_default(),
+        propstats: element
+            .dav_children("propstat")
+            .map(|propstat| PropStat {
+                prop: propstat
+                    .dav_child("prop")
+                    .map(parse_prop)
+                    .unwrap_or_default(),
+                status: propstat
+                    .dav_child("status")
+                    .map(Element::text)
+                    .unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+/// 不经过 `Prop` 的 `<propstat>`：状态行 + 按出现顺序的所有属性
+pub(crate) struct RawPropStat {
+    pub status: String,
+    pub props: Vec<(PropName, String)>,
+}
+
+/// 不经过 `Prop` 的 `<response>`
+pub(crate) struct RawResponse {
+    /// 整个资源的状态行，只有不带 `propstat` 的 response 才有
+    pub status: Option<String>,
+    pub propstats: Vec<RawPropStat>,
+}
+
+/// 按 response → propstat → prop 的顺序收集所有属性，属性名带命名空间
+/// - PROPPATCH 的响应里属性都是空元素，不需要解析成 `Prop` 的字段
+pub(crate) fn collect_responses(
+    xml: &str,
+) -> Result<Vec<RawResponse>, WebDavClientError> {
+    let document = parse_document(xml)?;
+
+    Ok(dav_responses(&document)?
+        .into_iter()
+        .map(|response| RawResponse {
+            status: response.dav_child("status").map(Element::text),
+            propstats: response
+                .dav_children("propstat")
+                .map(|propstat| RawPropStat {
+                    status: propstat
+                        .dav_child("status")
+                        .map(Element::text)
+                        .unwrap_or_default(),
+                    props: propstat
+                        .dav_child("prop")
+                        .map(|prop| {
+                            prop.children
+                                .iter()
+                                .map(|child| {
+                                    (
+                                        child.prop_name(),
+                                        prop_value(&child.raw),
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+impl MultiStatus {
+    /// 解析 207 Multi-Status 响应体，按命名空间识别属性
+    /// - 结构元素和标准属性按 `DAV:` 命名空间匹配，前缀任意，也支持默认命名空间
+    /// - 未知属性放进各自 `Prop` 的 `dead_props`
+    pub fn from_xml(xml: &str) -> Result<Self, WebDavClientError> {
+        let document = parse_document(xml)?;
+
+        Ok(MultiStatus {
+            responses: dav_responses(&document)?
+                .into_iter()
+                .map(parse_response)
+                .collect(),
+        })
+    }
+}
+
+impl Prop {
+    /// 解析根元素为 `<DAV:prop>` 的响应体，例如 LOCK 的响应
+    pub fn from_xml(xml: &str) -> Result<Self, WebDavClientError> {
+        let document = parse_document(xml)?;
+
+        document.dav_child("prop").map(parse_prop).ok_or_else(|| {
+            WebDavClientError::SerdeErr("响应体不是 prop".to_string())
+        })
+    }
+}
+
+impl PropStat {
+    /// 状态行里的状态码是否为 2xx
+    pub fn is_success(&self) -> bool {
+        status_code(&self.status)
+            .is_some_and(|code| (200..=299).contains(&code))
+    }
+}
@@ -0,0 +1,281 @@
+use crate::client::enums::client_enum::Depth;
+use crate::client::structs::raw_xml::ActiveLock;
+use quick_xml::escape::escape;
+use reqwest::Url;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 锁的范围
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockScope {
+    /// 独占锁，其他人无法再加任何锁
+    Exclusive,
+    /// 共享锁，其他人还能加共享锁
+    Shared,
+}
+
+impl LockScope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LockScope::Exclusive => "exclusive",
+            LockScope::Shared => "shared",
+        }
+    }
+}
+
+/// LOCK 请求的参数
+#[derive(Clone, Debug)]
+pub struct LockOptions {
+    pub scope: LockScope,
+    /// 只能是 `Depth::Zero` 或 `Depth::Infinity`，目录锁通常用 infinity
+    pub depth: Depth,
+    /// 申请的超时时间，`None` 表示不限（服务端可能改成更短的时间）
+    pub timeout: Option<Duration>,
+    /// 写进 `<owner>` 的所有者信息，方便别人知道是谁锁的
+    pub owner: Option<String>,
+}
+
+impl LockOptions {
+    pub fn new(
+        scope: LockScope,
+        depth: Depth,
+        timeout: Option<Duration>,
+        owner: Option<String>,
+    ) -> Self {
+        Self { scope, depth, timeout, owner }
+    }
+
+    /// 独占、Depth infinity、1 小时
+    pub fn new_default_config() -> Self {
+        Self {
+            scope: LockScope::Exclusive,
+            depth: Depth::Infinity,
+            timeout: Some(Duration::from_secs(3600)),
+            owner: None,
+        }
+    }
+
+    pub fn with_scope(mut self, scope: LockScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = Some(owner.to_string());
+        self
+    }
+
+    /// LOCK 请求体 `<lockinfo>`
+    pub(crate) fn to_xml(&self) -> String {
+        let owner = match &self.owner {
+            Some(owner) => {
+                format!("\n  <D:owner>{}</D:owner>", escape(owner))
+            }
+            None => String::new(),
+        };
+
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:lockinfo xmlns:D="DAV:">
+  <D:lockscope><D:{}/></D:lockscope>
+  <D:locktype><D:write/></D:locktype>{owner}
+</D:lockinfo>"#,
+            self.scope.as_str()
+        )
+    }
+}
+
+/// `Timeout` 请求头的值
+pub(crate) fn timeout_header(timeout: Option<Duration>) -> String {
+    match timeout {
+        Some(timeout) => format!("Second-{}", timeout.as_secs()),
+        None => "Infinite".to_string(),
+    }
+}
+
+/// 解析 `Second-3600`/`Infinite`，`Infinite` 和无法识别的值都按不限处理
+fn parse_timeout(timeout: &str) -> Option<Duration> {
+    timeout
+        .split(',')
+        .map(str::trim)
+        .find_map(|t| t.strip_prefix("Second-")?.parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// 一把锁的信息，来自 LOCK 响应或 `lockdiscovery`
+#[derive(Clone, Debug)]
+pub struct LockInfo {
+    /// 加锁的资源（完整 URL）
+    pub url: String,
+    /// 锁令牌，不带尖括号
+    pub token: String,
+    pub scope: LockScope,
+    pub depth: Depth,
+    pub owner: Option<String>,
+    /// 服务端给出的超时时间，`None` 表示不限
+    pub timeout: Option<Duration>,
+}
+
+impl LockInfo {
+    /// 从 `<activelock>` 整理，`request_url` 用来解析相对的 `lockroot`
+    /// - 没有锁令牌时返回 `None`
+    pub fn from_active_lock(
+        active_lock: &ActiveLock,
+        request_url: &str,
+    ) -> Option<Self> {
+        let token =
+            active_lock.lock_token.as_ref()?.href.trim().to_string();
+
+        let url = match &active_lock.lock_root {
+            Some(root) => Url::parse(request_url)
+                .and_then(|base| base.join(root.href.trim()))
+                .map(|url| url.to_string())
+                .unwrap_or_else(|_| request_url.to_string()),
+            None => request_url.to_string(),
+        };
+
+        let scope = match &active_lock.lock_scope {
+            Some(scope) if scope.shared.is_some() => LockScope::Shared,
+            _ => LockScope::Exclusive,
+        };
+
+        let depth = match active_lock.depth.as_deref().map(str::trim) {
+            Some("0") => Depth::Zero,
+            _ => Depth::Infinity,
+        };
+
+        let owner = active_lock.owner.as_ref().and_then(|owner| {
+            owner
+                .href
+                .clone()
+                .or_else(|| owner.text.clone())
+                .map(|owner| owner.trim().to_string())
+        });
+
+        Some(Self {
+            url,
+            token,
+            scope,
+            depth,
+            owner,
+            timeout: active_lock
+                .timeout
+                .as_deref()
+                .and_then(parse_timeout),
+        })
+    }
+
+    /// 写 `url` 时是否需要这把锁的令牌
+    /// - 锁住的资源本身，以及深度锁下的所有子项
+    /// - Depth 0 锁住的目录，往里面增删直接子项同样需要令牌
+    fn covers(&self, url: &str) -> bool {
+        let root = self.url.trim_end_matches('/');
+        let url = url.trim_end_matches('/');
+
+        let Some(relative) =
+            url.strip_prefix(root).and_then(|rest| rest.strip_prefix('/'))
+        else {
+            return url == root;
+        };
+
+        self.depth == Depth::Infinity || !relative.contains('/')
+    }
+}
+
+struct HeldLock {
+    info: LockInfo,
+    expires_at: Option<Instant>,
+}
+
+/// 账号当前持有的锁
+/// - clone 后共享同一份，写请求发出前按 URL 取出需要提交的令牌
+#[derive(Clone, Default)]
+pub(crate) struct LockStore {
+    locks: Arc<Mutex<Vec<HeldLock>>>,
+}
+
+impl LockStore {
+    fn held(&self) -> std::sync::MutexGuard<'_, Vec<HeldLock>> {
+        let mut locks =
+            self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        locks.retain(|lock| lock.expires_at.is_none_or(|at| at > now));
+        locks
+    }
+
+    /// 记录新拿到（或刷新后）的锁，同一个令牌只保留一条
+    pub fn insert(&self, info: LockInfo) {
+        let expires_at =
+            info.timeout.map(|timeout| Instant::now() + timeout);
+        let mut locks = self.held();
+        locks.retain(|lock| lock.info.token != info.token);
+        locks.push(HeldLock { info, expires_at });
+    }
+
+    pub fn remove(&self, token: &str) {
+        self.held().retain(|lock| lock.info.token != token);
+    }
+
+    /// 锁住的就是 `url` 本身的那把锁
+    pub fn find(&self, url: &str) -> Option<LockInfo> {
+        let url = url.trim_end_matches('/');
+        self.held()
+            .iter()
+            .find(|lock| lock.info.url.trim_end_matches('/') == url)
+            .map(|lock| lock.info.clone())
+    }
+
+    pub fn list(&self) -> Vec<LockInfo> {
+        self.held().iter().map(|lock| lock.info.clone()).collect()
+    }
+
+    /// 写请求需要的 `If` 头，没有相关的锁时返回 `None`
+    /// - 只涉及请求地址本身的锁时用 `(<token>)`
+    /// - 涉及上级目录的锁或 MOVE/COPY 的目标时按锁住的资源打标签：`<url> (<token>)`
+    pub fn if_header(
+        &self,
+        url: &str,
+        destination: Option<&str>,
+    ) -> Option<String> {
+        let locks = self.held();
+        let relevant: Vec<&LockInfo> = locks
+            .iter()
+            .map(|lock| &lock.info)
+            .filter(|info| {
+                info.covers(url)
+                    || destination.is_some_and(|d| info.covers(d))
+            })
+            .collect();
+
+        if relevant.is_empty() {
+            return None;
+        }
+
+        let untagged = relevant.iter().all(|info| {
+            info.url.trim_end_matches('/') == url.trim_end_matches('/')
+        });
+
+        let lists: Vec<String> = relevant
+            .iter()
+            .map(|info| {
+                if untagged {
+                    format!("(<{}>)", info.token)
+                } else {
+                    format!("<{}> (<{}>)", info.url, info.token)
+                }
+            })
+            .collect();
+
+        Some(lists.join(" "))
+    }
+}
@@ -1,5 +1,23 @@
 pub mod raw_xml;
 pub mod friendly_xml;
 pub mod impl_raw_xml;
+pub mod multi_status_stream;
 
+pub mod auth;
+pub mod capabilities;
+pub mod checksum;
+pub mod digest_auth;
+pub mod http_client;
+pub mod http_date;
+pub mod lock;
+pub mod progress;
+pub mod propfind;
+pub mod proppatch;
+pub mod quota;
+pub mod provider_profile;
+pub mod rate_limiter;
+pub mod remote_tree;
+pub mod retry_policy;
+pub mod transfer_control;
+pub mod transfer_pool;
 pub mod webdav_child_client;
\ No newline at end of file
@@ -0,0 +1,178 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::raw_xml::{MultiStatus, Response};
+use quick_xml::Error;
+use quick_xml::errors::IllFormedError;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+/// 扫描缓冲区一次的结果
+enum Scan {
+    /// 遇到根元素的开始标签，`[start, end)` 是标签本身
+    Root { start: usize, end: usize, empty: bool },
+    /// `[start, end)` 是一个完整的 `<response>` 元素
+    Response { start: usize, end: usize },
+    /// `[0, consumed)` 已处理完（空白、注释、其他顶层元素），剩下的数据还不完整
+    Incomplete { consumed: usize },
+}
+
+/// 207 Multi-Status 的增量解析器：边收数据边切出完整的 `<response>`
+/// - 只缓存还没解析完的部分，内存占用和单个 `<response>` 的大小相当，和列表长度无关
+/// - 每个 `<response>` 套上根元素的开始标签（带命名空间声明）后交给 [`MultiStatus::from_xml`]，
+///   和一次性解析的结果一致，`dead_props` 也照常收集
+#[derive(Debug, Default)]
+pub struct MultiStatusParser {
+    /// 收到但还没解析的数据
+    buf: Vec<u8>,
+    /// 根元素的开始标签，例如 `<D:multistatus xmlns:D="DAV:">`
+    root_start: Option<String>,
+    /// 根元素的结束标签
+    root_end: String,
+    /// 根元素已结束（或是空元素）
+    finished: bool,
+}
+
+impl MultiStatusParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加收到的数据
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// 取出下一个完整的 `<response>`，数据还不够时返回 `None`
+    pub fn next_response(
+        &mut self,
+    ) -> Result<Option<Response>, WebDavClientError> {
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+
+            match self.scan() {
+                Scan::Root { start, end, empty } => {
+                    let start_tag =
+                        String::from_utf8_lossy(&self.buf[start..end])
+                            .to_string();
+                    self.root_end = root_end_tag(&start_tag);
+                    if !self.root_end.ends_with(":multistatus>")
+                        && self.root_end != "</multistatus>"
+                    {
+                        return Err(WebDavClientError::String(format!(
+                            "响应体不是 multistatus: {start_tag}"
+                        )));
+                    }
+                    self.root_start = Some(start_tag);
+                    self.finished = empty;
+                    self.buf.drain(..end);
+                }
+                Scan::Response { start, end } => {
+                    let fragment =
+                        std::str::from_utf8(&self.buf[start..end])
+                            .map_err(|e| {
+                                WebDavClientError::SerdeErr(e.to_string())
+                            })?;
+                    let xml = format!(
+                        "{}{fragment}{}",
+                        self.root_start.as_deref().unwrap_or_default(),
+                        self.root_end
+                    );
+                    let response = MultiStatus::from_xml(&xml)?
+                        .responses
+                        .into_iter()
+                        .next();
+                    self.buf.drain(..end);
+
+                    if response.is_some() {
+                        return Ok(response);
+                    }
+                }
+                Scan::Incomplete { consumed } => {
+                    self.buf.drain(..consumed);
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// 数据已经收完，还有没解析的内容说明响应体不完整或格式错误
+    pub fn finish(&self) -> Result<(), WebDavClientError> {
+        let rest = String::from_utf8_lossy(&self.buf);
+        if self.root_start.is_none() {
+            return Err(WebDavClientError::String(format!(
+                "响应体不是 multistatus: {rest}"
+            )));
+        }
+        if !self.finished && !rest.trim().is_empty() {
+            return Err(WebDavClientError::String(format!(
+                "multistatus 响应体不完整: {rest}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// 从缓冲区开头往后读，直到找到根元素、一个完整的 `<response>`，或者数据不够
+    fn scan(&mut self) -> Scan {
+        let mut reader = Reader::from_reader(self.buf.as_slice());
+        let mut consumed = 0;
+
+        loop {
+            let start = reader.buffer_position() as usize;
+
+            let event = match reader.read_event() {
+                Ok(event) => event,
+                // 根元素的开始标签已经移出缓冲区，它的结束标签在这里没有对应的开始标签
+                Err(Error::IllFormed(
+                    IllFormedError::UnmatchedEndTag(_),
+                )) => {
+                    self.finished = true;
+                    return Scan::Incomplete {
+                        consumed: reader.buffer_position() as usize,
+                    };
+                }
+                // 缓冲区末尾的标签可能只收到一半，等更多数据再试
+                Err(_) => return Scan::Incomplete { consumed },
+            };
+
+            match event {
+                Event::Start(_) | Event::Empty(_)
+                    if self.root_start.is_none() =>
+                {
+                    return Scan::Root {
+                        start,
+                        end: reader.buffer_position() as usize,
+                        empty: matches!(event, Event::Empty(_)),
+                    };
+                }
+                Event::Start(e) => {
+                    let is_response =
+                        e.local_name().as_ref() == b"response";
+                    if reader.read_to_end(e.name()).is_err() {
+                        return Scan::Incomplete { consumed };
+                    }
+                    let end = reader.buffer_position() as usize;
+                    if is_response {
+                        return Scan::Response { start, end };
+                    }
+                    // 顶层的其他元素（如 responsedescription、sync-token）跳过
+                    consumed = end;
+                }
+                Event::Eof => return Scan::Incomplete { consumed },
+                // 文本可能被截断，只在后面还有完整事件时才算处理完
+                Event::Text(_) => {}
+                _ => consumed = reader.buffer_position() as usize,
+            }
+        }
+    }
+}
+
+/// 按开始标签生成对应的结束标签，`<D:multistatus xmlns:D="DAV:">` → `</D:multistatus>`
+fn root_end_tag(start_tag: &str) -> String {
+    let name: String = start_tag
+        .trim_start_matches('<')
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+        .collect();
+    format!("</{name}>")
+}
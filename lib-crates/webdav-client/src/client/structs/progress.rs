@@ -0,0 +1,283 @@
+use crate::client::error::WebDavClientError;
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc;
+
+/// 同一个文件至少传输这么多字节才发一次 `BytesTransferred`，避免事件刷屏
+const PROGRESS_INTERVAL: u64 = 256 * 1024;
+
+/// 传输进度事件，上传和下载共用
+/// - 可以直接序列化成 JSON 推给前端（`type` 字段区分事件）
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransferEvent {
+    /// 开始传输一个文件，`resumed` 为续传时已有的字节数
+    FileStarted {
+        remote_url: String,
+        local_path: String,
+        size: Option<u64>,
+        resumed: u64,
+    },
+    /// 文件已传输的字节数（累计值，包含续传前已有的部分）
+    BytesTransferred {
+        remote_url: String,
+        transferred: u64,
+        size: Option<u64>,
+    },
+    /// 文件传输完成
+    FileFinished { remote_url: String, local_path: String, bytes: u64 },
+    /// 本地已有完整文件，跳过
+    FileSkipped { remote_url: String, local_path: String },
+    /// 文件传输失败
+    FileFailed { remote_url: String, local_path: String, error: String },
+    /// 本次调用的汇总，每次有文件开始/结束以及字节进度更新时发送
+    Totals(TransferTotals),
+}
+
+/// 本次调用的汇总
+/// - `files_total`/`bytes_total` 会随着目录展开逐步增加
+/// - 大小未知的文件不计入 `bytes_total`
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct TransferTotals {
+    pub files_total: u64,
+    pub files_finished: u64,
+    pub files_failed: u64,
+    pub bytes_total: u64,
+    pub bytes_transferred: u64,
+}
+
+pub type TProgressSender = mpsc::UnboundedSender<TransferEvent>;
+pub type TProgressReceiver = mpsc::UnboundedReceiver<TransferEvent>;
+
+/// 创建一对进度通道，发送端放进 `DownloadConfig`/`UploadConfig`
+pub fn progress_channel() -> (TProgressSender, TProgressReceiver) {
+    mpsc::unbounded_channel()
+}
+
+#[derive(Default)]
+struct TotalsCounter {
+    files_total: AtomicU64,
+    files_finished: AtomicU64,
+    files_failed: AtomicU64,
+    bytes_total: AtomicU64,
+    bytes_transferred: AtomicU64,
+}
+
+/// 一次上传/下载调用的进度汇报器
+/// - 没有发送端时所有方法都是空操作
+/// - clone 后共享同一份汇总，可以 move 进传输池的任务
+#[derive(Clone, Default)]
+pub struct Progress {
+    sender: Option<TProgressSender>,
+    totals: Arc<TotalsCounter>,
+}
+
+impl Progress {
+    pub fn new(sender: Option<TProgressSender>) -> Self {
+        Self { sender, totals: Arc::default() }
+    }
+
+    fn send(&self, event: TransferEvent) {
+        if let Some(sender) = &self.sender {
+            // 接收端已经丢弃时忽略，不影响传输本身
+            let _ = sender.send(event);
+        }
+    }
+
+    pub fn totals(&self) -> TransferTotals {
+        let totals = &self.totals;
+        TransferTotals {
+            files_total: totals.files_total.load(Ordering::Relaxed),
+            files_finished: totals.files_finished.load(Ordering::Relaxed),
+            files_failed: totals.files_failed.load(Ordering::Relaxed),
+            bytes_total: totals.bytes_total.load(Ordering::Relaxed),
+            bytes_transferred: totals
+                .bytes_transferred
+                .load(Ordering::Relaxed),
+        }
+    }
+
+    fn send_totals(&self) {
+        if self.sender.is_some() {
+            self.send(TransferEvent::Totals(self.totals()));
+        }
+    }
+
+    /// 登记待传输的文件（按 `FriendlyResource::size` 等已知大小）
+    pub fn add_files<I>(&self, sizes: I)
+    where
+        I: IntoIterator<Item = Option<u64>>,
+    {
+        let mut added = false;
+        for size in sizes {
+            added = true;
+            self.totals.files_total.fetch_add(1, Ordering::Relaxed);
+            self.totals
+                .bytes_total
+                .fetch_add(size.unwrap_or(0), Ordering::Relaxed);
+        }
+        if added {
+            self.send_totals();
+        }
+    }
+
+    /// 开始传输一个文件
+    pub fn start_file(
+        &self,
+        remote_url: &str,
+        local_path: &str,
+        size: Option<u64>,
+        resumed: u64,
+    ) -> FileProgress {
+        self.totals
+            .bytes_transferred
+            .fetch_add(resumed, Ordering::Relaxed);
+
+        self.send(TransferEvent::FileStarted {
+            remote_url: remote_url.to_string(),
+            local_path: local_path.to_string(),
+            size,
+            resumed,
+        });
+        self.send_totals();
+
+        FileProgress {
+            inner: Arc::new(FileProgressInner {
+                progress: self.clone(),
+                remote_url: remote_url.to_string(),
+                local_path: local_path.to_string(),
+                size,
+                transferred: AtomicU64::new(resumed),
+                last_emitted: AtomicU64::new(resumed),
+            }),
+        }
+    }
+
+    /// 跳过一个文件，按已完成计入汇总
+    pub fn skip_file(
+        &self,
+        remote_url: &str,
+        local_path: &str,
+        size: Option<u64>,
+    ) {
+        self.totals.files_finished.fetch_add(1, Ordering::Relaxed);
+        self.totals
+            .bytes_transferred
+            .fetch_add(size.unwrap_or(0), Ordering::Relaxed);
+        self.send(TransferEvent::FileSkipped {
+            remote_url: remote_url.to_string(),
+            local_path: local_path.to_string(),
+        });
+        self.send_totals();
+    }
+
+    /// 文件还没开始传输就失败了（例如打开本地文件失败）
+    pub fn fail_file(
+        &self,
+        remote_url: &str,
+        local_path: &str,
+        error: &WebDavClientError,
+    ) {
+        self.totals.files_failed.fetch_add(1, Ordering::Relaxed);
+        self.send(TransferEvent::FileFailed {
+            remote_url: remote_url.to_string(),
+            local_path: local_path.to_string(),
+            error: error.to_string(),
+        });
+        self.send_totals();
+    }
+}
+
+struct FileProgressInner {
+    progress: Progress,
+    remote_url: String,
+    local_path: String,
+    size: Option<u64>,
+    transferred: AtomicU64,
+    last_emitted: AtomicU64,
+}
+
+/// 单个文件的进度，由 [`Progress::start_file`] 创建
+/// - 方法都只需要 `&self`，并发分片可以共用同一个
+#[derive(Clone)]
+pub struct FileProgress {
+    inner: Arc<FileProgressInner>,
+}
+
+impl FileProgress {
+    /// 又传输了 `delta` 字节
+    pub fn add(&self, delta: u64) {
+        let inner = &self.inner;
+        let transferred =
+            inner.transferred.fetch_add(delta, Ordering::Relaxed) + delta;
+        inner
+            .progress
+            .totals
+            .bytes_transferred
+            .fetch_add(delta, Ordering::Relaxed);
+
+        let last_emitted = inner.last_emitted.load(Ordering::Relaxed);
+        if transferred.saturating_sub(last_emitted) >= PROGRESS_INTERVAL
+            && inner
+                .last_emitted
+                .compare_exchange(
+                    last_emitted,
+                    transferred,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+        {
+            self.emit(transferred);
+        }
+    }
+
+    /// 已传输的部分作废（远端变化或服务端忽略 Range），从 0 重新计
+    pub fn reset(&self) {
+        let inner = &self.inner;
+        let transferred = inner.transferred.swap(0, Ordering::Relaxed);
+        inner.last_emitted.store(0, Ordering::Relaxed);
+        inner
+            .progress
+            .totals
+            .bytes_transferred
+            .fetch_sub(transferred, Ordering::Relaxed);
+        self.emit(0);
+    }
+
+    fn emit(&self, transferred: u64) {
+        let inner = &self.inner;
+        inner.progress.send(TransferEvent::BytesTransferred {
+            remote_url: inner.remote_url.clone(),
+            transferred,
+            size: inner.size,
+        });
+        inner.progress.send_totals();
+    }
+
+    pub fn finish(&self) {
+        let inner = &self.inner;
+        let bytes = inner.transferred.load(Ordering::Relaxed);
+        inner
+            .progress
+            .totals
+            .files_finished
+            .fetch_add(1, Ordering::Relaxed);
+        inner.progress.send(TransferEvent::FileFinished {
+            remote_url: inner.remote_url.clone(),
+            local_path: inner.local_path.clone(),
+            bytes,
+        });
+        inner.progress.send_totals();
+    }
+
+    pub fn fail(&self, error: &WebDavClientError) {
+        let inner = &self.inner;
+        inner.progress.fail_file(
+            &inner.remote_url,
+            &inner.local_path,
+            error,
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use quick_xml::escape::escape;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// `DAV:` 命名空间
+pub const DAV_NAMESPACE: &str = "DAV:";
+
+/// Nextcloud/ownCloud 的扩展命名空间
+pub const OC_NAMESPACE: &str = "http://owncloud.org/ns";
+
+/// 属性的限定名：命名空间 + 本地名
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PropName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PropName {
+    pub fn new(namespace: &str, name: &str) -> Self {
+        Self { namespace: namespace.to_string(), name: name.to_string() }
+    }
+
+    /// `DAV:` 命名空间下的属性
+    pub fn dav(name: &str) -> Self {
+        Self::new(DAV_NAMESPACE, name)
+    }
+
+    /// 解析 Clark 记法 `{命名空间}名字`，没有命名空间时按 `DAV:` 处理
+    pub fn parse_clark(clark: &str) -> Self {
+        match clark.strip_prefix('{').and_then(|rest| rest.split_once('}'))
+        {
+            Some((namespace, name)) => Self::new(namespace, name),
+            None => Self::dav(clark),
+        }
+    }
+}
+
+/// 输出为 Clark 记法
+impl Display for PropName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{}}}{}", self.namespace, self.name)
+    }
+}
+
+/// 序列化为 Clark 记法，作为 map 的键时也能输出成 JSON
+impl Serialize for PropName {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// `Prop` 没有对应字段的属性（自定义属性、死属性），值为元素内的原始内容
+/// - 纯文本会反转义；带子元素时保留原始 XML
+pub type TDeadProps = BTreeMap<PropName, String>;
+
+/// 请求体里的命名空间前缀：`DAV:` 用 `D`，其他命名空间按出现顺序用 `x0`、`x1`…
+pub(crate) struct NamespacePrefixes<'a> {
+    namespaces: Vec<&'a str>,
+}
+
+impl<'a> NamespacePrefixes<'a> {
+    pub fn new(props: impl IntoIterator<Item = &'a PropName>) -> Self {
+        let mut namespaces: Vec<&str> = Vec::new();
+        for prop in props {
+            if prop.namespace != DAV_NAMESPACE
+                && !namespaces.contains(&prop.namespace.as_str())
+            {
+                namespaces.push(&prop.namespace);
+            }
+        }
+        Self { namespaces }
+    }
+
+    /// 声明在根元素上的 ` xmlns:x0="..."`
+    pub fn declarations(&self) -> String {
+        self.namespaces
+            .iter()
+            .enumerate()
+            .map(|(index, namespace)| {
+                format!(r#" xmlns:x{index}="{}""#, escape(*namespace))
+            })
+            .collect()
+    }
+
+    /// 带前缀的元素名，如 `D:getetag`、`x0:mtime`
+    pub fn qualified(&self, prop: &PropName) -> String {
+        match self
+            .namespaces
+            .iter()
+            .position(|namespace| *namespace == prop.namespace)
+        {
+            Some(index) => format!("x{index}:{}", prop.name),
+            None => format!("D:{}", prop.name),
+        }
+    }
+}
+
+/// PROPFIND 请求的三种形式（RFC 4918 第 9.1 节）
+#[derive(Clone, Debug)]
+pub enum PropfindMode {
+    /// `<allprop/>`，`include` 里的属性一并返回（有的服务端 allprop 不返回它们）
+    AllProp { include: Vec<PropName> },
+    /// `<prop>`，只返回列出的属性
+    Prop(Vec<PropName>),
+    /// `<propname/>`，只返回属性名不返回值
+    PropName,
+}
+
+/// PROPFIND 请求体的构建器
+/// ```
+/// use webdav_client::client::structs::propfind::PropfindRequest;
+///
+/// let request = PropfindRequest::prop()
+///     .with_dav_prop("getetag")
+///     .with_prop("http://owncloud.org/ns", "checksums");
+/// assert!(request.to_xml().contains("<D:getetag/>"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PropfindRequest {
+    pub mode: PropfindMode,
+}
+
+impl Default for PropfindRequest {
+    fn default() -> Self {
+        Self::allprop()
+    }
+}
+
+impl PropfindRequest {
+    pub fn allprop() -> Self {
+        Self { mode: PropfindMode::AllProp { include: Vec::new() } }
+    }
+
+    /// 只请求指定属性，之后用 `with_*` 添加
+    pub fn prop() -> Self {
+        Self { mode: PropfindMode::Prop(Vec::new()) }
+    }
+
+    pub fn propname() -> Self {
+        Self { mode: PropfindMode::PropName }
+    }
+
+    /// 添加一个属性；allprop 时加到 `include`，propname 时忽略
+    pub fn with_prop_name(mut self, prop_name: PropName) -> Self {
+        match &mut self.mode {
+            PropfindMode::AllProp { include: props }
+            | PropfindMode::Prop(props) => {
+                if !props.contains(&prop_name) {
+                    props.push(prop_name);
+                }
+            }
+            PropfindMode::PropName => {}
+        }
+        self
+    }
+
+    pub fn with_prop(self, namespace: &str, name: &str) -> Self {
+        self.with_prop_name(PropName::new(namespace, name))
+    }
+
+    pub fn with_dav_prop(self, name: &str) -> Self {
+        self.with_prop_name(PropName::dav(name))
+    }
+
+    pub fn with_props(
+        self,
+        prop_names: impl IntoIterator<Item = PropName>,
+    ) -> Self {
+        prop_names.into_iter().fold(self, Self::with_prop_name)
+    }
+
+    /// 列目录常用的属性：类型、大小、时间、MIME、ETag、显示名
+    pub fn with_basic_props(self) -> Self {
+        self.with_props(
+            [
+                "resourcetype",
+                "getcontentlength",
+                "getlastmodified",
+                "getcontenttype",
+                "getetag",
+                "displayname",
+                "creationdate",
+            ]
+            .map(PropName::dav),
+        )
+    }
+
+    /// RFC 4331 配额属性
+    pub fn with_quota_props(self) -> Self {
+        self.with_props(
+            ["quota-available-bytes", "quota-used-bytes"]
+                .map(PropName::dav),
+        )
+    }
+
+    /// 生成请求体，命名空间都声明在根元素上
+    pub fn to_xml(&self) -> String {
+        let props: &[PropName] = match &self.mode {
+            PropfindMode::AllProp { include } => include,
+            PropfindMode::Prop(props) => props,
+            PropfindMode::PropName => &[],
+        };
+
+        let prefixes = NamespacePrefixes::new(props);
+
+        let elements: String = props
+            .iter()
+            .map(|prop| format!("\n    <{}/>", prefixes.qualified(prop)))
+            .collect();
+
+        let body = match &self.mode {
+            PropfindMode::AllProp { include } if include.is_empty() => {
+                "\n  <D:allprop/>".to_string()
+            }
+            PropfindMode::AllProp { .. } => format!(
+                "\n  <D:allprop/>\n  <D:include>{elements}\n  </D:include>"
+            ),
+            PropfindMode::Prop(_) => {
+                format!("\n  <D:prop>{elements}\n  </D:prop>")
+            }
+            PropfindMode::PropName => "\n  <D:propname/>".to_string(),
+        };
+
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:propfind xmlns:D="DAV:"{}>{body}
+</D:propfind>"#,
+            prefixes.declarations()
+        )
+    }
+}
@@ -0,0 +1,199 @@
+use crate::client::structs::impl_raw_xml::{RawResponse, status_code};
+use crate::client::structs::propfind::{NamespacePrefixes, PropName};
+use quick_xml::escape::escape;
+use serde::Serialize;
+
+/// PROPPATCH 里的一条修改
+#[derive(Clone, Debug)]
+pub enum PropPatchOp {
+    /// 设置属性，值按纯文本写入
+    Set(PropName, String),
+    /// 删除属性，属性不存在也算成功
+    Remove(PropName),
+}
+
+impl PropPatchOp {
+    pub fn prop_name(&self) -> &PropName {
+        match self {
+            PropPatchOp::Set(prop_name, _)
+            | PropPatchOp::Remove(prop_name) => prop_name,
+        }
+    }
+}
+
+/// PROPPATCH 请求体的构建器
+/// - 服务端按顺序执行，要么全部成功，要么全部不生效（RFC 4918 第 9.2 节）
+/// - `DAV:` 下的属性大多是只读的活属性，修改会被拒绝
+/// ```
+/// use webdav_client::client::structs::proppatch::PropPatchRequest;
+///
+/// let request = PropPatchRequest::new()
+///     .with_set("urn:example:sync", "mtime", "1700000000")
+///     .with_remove("urn:example:sync", "origin");
+/// assert!(request.to_xml().contains("<x0:mtime>1700000000</x0:mtime>"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PropPatchRequest {
+    pub ops: Vec<PropPatchOp>,
+}
+
+impl PropPatchRequest {
+    pub fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    pub fn with_set_prop(
+        mut self,
+        prop_name: PropName,
+        value: &str,
+    ) -> Self {
+        self.ops.push(PropPatchOp::Set(prop_name, value.to_string()));
+        self
+    }
+
+    pub fn with_set(
+        self,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) -> Self {
+        self.with_set_prop(PropName::new(namespace, name), value)
+    }
+
+    pub fn with_remove_prop(mut self, prop_name: PropName) -> Self {
+        self.ops.push(PropPatchOp::Remove(prop_name));
+        self
+    }
+
+    pub fn with_remove(self, namespace: &str, name: &str) -> Self {
+        self.with_remove_prop(PropName::new(namespace, name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// 生成请求体，每条修改一个 `<set>`/`<remove>`，保持顺序
+    pub fn to_xml(&self) -> String {
+        let prefixes = NamespacePrefixes::new(
+            self.ops.iter().map(|op| op.prop_name()),
+        );
+
+        let body: String = self
+            .ops
+            .iter()
+            .map(|op| match op {
+                PropPatchOp::Set(prop_name, value) => {
+                    let name = prefixes.qualified(prop_name);
+                    format!(
+                        "\n  <D:set>\n    <D:prop><{name}>{}</{name}></D:prop>\n  </D:set>",
+                        escape(value.as_str())
+                    )
+                }
+                PropPatchOp::Remove(prop_name) => format!(
+                    "\n  <D:remove>\n    <D:prop><{}/></D:prop>\n  </D:remove>",
+                    prefixes.qualified(prop_name)
+                ),
+            })
+            .collect();
+
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:propertyupdate xmlns:D="DAV:"{}>{body}
+</D:propertyupdate>"#,
+            prefixes.declarations()
+        )
+    }
+}
+
+/// 单个属性的修改结果
+#[derive(Clone, Debug, Serialize)]
+pub struct PropPatchStatus {
+    pub name: PropName,
+    /// 200 成功；403 不允许修改；409 值不合法；424 因为其他属性失败而没有执行
+    pub status: u16,
+}
+
+impl PropPatchStatus {
+    pub fn is_success(&self) -> bool {
+        (200..=299).contains(&self.status)
+    }
+}
+
+/// PROPPATCH 的结果
+#[derive(Clone, Debug, Serialize)]
+pub struct PropPatchResult {
+    /// 请求的资源（完整 URL）
+    pub url: String,
+    pub props: Vec<PropPatchStatus>,
+}
+
+impl PropPatchResult {
+    /// 所有属性都修改成功
+    pub fn is_success(&self) -> bool {
+        self.props.iter().all(PropPatchStatus::is_success)
+    }
+
+    /// 修改失败的属性
+    pub fn failed(&self) -> Vec<&PropPatchStatus> {
+        self.props.iter().filter(|prop| !prop.is_success()).collect()
+    }
+
+    /// 服务端直接返回 2xx 没有响应体时，按全部成功处理
+    pub(crate) fn all_success(
+        url: &str,
+        request: &PropPatchRequest,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            props: request
+                .ops
+                .iter()
+                .map(|op| PropPatchStatus {
+                    name: op.prop_name().clone(),
+                    status: 200,
+                })
+                .collect(),
+        }
+    }
+
+    /// 从 207 响应整理每个属性的状态
+    /// - 只有整个资源的状态（如 423）时所有属性都记为该状态
+    /// - 响应里缺少的属性记为 424，提醒调用方没有生效
+    pub(crate) fn from_responses(
+        url: &str,
+        request: &PropPatchRequest,
+        responses: &[RawResponse],
+    ) -> Self {
+        let statuses: Vec<(&PropName, u16)> = responses
+            .iter()
+            .flat_map(|response| &response.propstats)
+            .flat_map(|propstat| {
+                let status = status_code(&propstat.status).unwrap_or(500);
+                propstat.props.iter().map(move |(name, _)| (name, status))
+            })
+            .collect();
+
+        let fallback = responses
+            .iter()
+            .find_map(|response| response.status.as_deref())
+            .and_then(status_code)
+            .unwrap_or(424);
+
+        let props = request
+            .ops
+            .iter()
+            .map(|op| {
+                let name = op.prop_name();
+                let status = statuses
+                    .iter()
+                    .find(|(prop_name, _)| *prop_name == name)
+                    .map(|(_, status)| *status)
+                    .unwrap_or(fallback);
+                PropPatchStatus { name: name.clone(), status }
+            })
+            .collect();
+
+        Self { url: url.to_string(), props }
+    }
+}
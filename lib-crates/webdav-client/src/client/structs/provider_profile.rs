@@ -0,0 +1,257 @@
+use crate::client::error::WebDavClientError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 请求频率上限：`per_secs` 秒内最多 `max_requests` 个请求
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub max_requests: u32,
+    pub per_secs: u64,
+}
+
+impl RateLimit {
+    pub fn new(max_requests: u32, per_secs: u64) -> Self {
+        Self { max_requests, per_secs }
+    }
+}
+
+/// 服务商的已知限制，添加账号时按 `base_url` 匹配
+/// - 配置文件里没写的字段取默认值（没有限制）
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProviderProfile {
+    pub name: String,
+    /// 匹配规则
+    /// - 以 `http://`/`https://` 开头的按 `base_url` 前缀匹配
+    /// - 其他按 `base_url` 是否包含该片段匹配，例如 `/remote.php/dav/`
+    pub match_urls: Vec<String>,
+    /// 不使用 Range 分片下载
+    pub disable_ranges: bool,
+    pub rate_limit: Option<RateLimit>,
+    /// 文件名里不允许出现的字符
+    pub forbidden_chars: Vec<char>,
+    /// 上传时替换不允许字符所用的字符
+    pub replacement_char: char,
+    /// 单个文件的大小上限（字节）
+    pub max_file_size: Option<u64>,
+    /// 需要在 PROPFIND 里显式请求的校验和属性，Clark 记法 `{命名空间}名字`
+    pub checksum_props: Vec<String>,
+    /// 上传时带 `X-OC-Mtime`（Unix 秒）让服务端保留本地修改时间
+    /// - 服务端接受时响应头会带 `X-OC-MTime: accepted`
+    pub oc_mtime_header: bool,
+    /// 上传后用 PROPPATCH 写入修改时间的属性，Clark 记法，值为 HTTP-date
+    /// - 例如 `{DAV:}getlastmodified`、`{urn:schemas-microsoft-com:}Win32LastModifiedTime`
+    /// - 已经通过 `X-OC-Mtime` 保留时不再发送
+    pub mtime_props: Vec<String>,
+}
+
+impl Default for ProviderProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            match_urls: Vec::new(),
+            disable_ranges: false,
+            rate_limit: None,
+            forbidden_chars: Vec::new(),
+            replacement_char: '_',
+            max_file_size: None,
+            checksum_props: Vec::new(),
+            oc_mtime_header: false,
+            mtime_props: Vec::new(),
+        }
+    }
+}
+
+impl ProviderProfile {
+    pub fn new(name: &str, match_urls: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            match_urls: match_urls.iter().map(|u| u.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_disable_ranges(mut self, disable_ranges: bool) -> Self {
+        self.disable_ranges = disable_ranges;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    pub fn with_forbidden_chars(
+        mut self,
+        forbidden_chars: &[char],
+    ) -> Self {
+        self.forbidden_chars = forbidden_chars.to_vec();
+        self
+    }
+
+    pub fn with_replacement_char(
+        mut self,
+        replacement_char: char,
+    ) -> Self {
+        self.replacement_char = replacement_char;
+        self
+    }
+
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    pub fn with_checksum_props(mut self, checksum_props: &[&str]) -> Self {
+        self.checksum_props =
+            checksum_props.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    pub fn with_oc_mtime_header(mut self, oc_mtime_header: bool) -> Self {
+        self.oc_mtime_header = oc_mtime_header;
+        self
+    }
+
+    pub fn with_mtime_props(mut self, mtime_props: &[&str]) -> Self {
+        self.mtime_props =
+            mtime_props.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// 是否适用于该账号地址
+    pub fn matches(&self, base_url: &str) -> bool {
+        self.match_urls.iter().any(|pattern| {
+            if pattern.starts_with("http://")
+                || pattern.starts_with("https://")
+            {
+                base_url.starts_with(pattern.as_str())
+            } else {
+                base_url.contains(pattern.as_str())
+            }
+        })
+    }
+
+    /// 把不允许的字符替换掉，上传时生成远端文件名用
+    pub fn sanitize_name(&self, name: &str) -> String {
+        name.chars()
+            .map(|c| {
+                if self.forbidden_chars.contains(&c) {
+                    self.replacement_char
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    /// 用户指定的名字（新建目录、重命名）里有不允许的字符时报错，不擅自改名
+    pub fn check_name(&self, name: &str) -> Result<(), WebDavClientError> {
+        match name.chars().find(|c| self.forbidden_chars.contains(c)) {
+            Some(c) => Err(WebDavClientError::String(format!(
+                "{} 不允许文件名包含字符 {c:?}: {name}",
+                self.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn check_file_size(
+        &self,
+        size: u64,
+    ) -> Result<(), WebDavClientError> {
+        match self.max_file_size {
+            Some(max) if size > max => Err(WebDavClientError::String(
+                format!("文件大小 {size} 超过 {} 的上限 {max}", self.name),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 配置文件格式：`{ "profiles": [ ... ] }`
+#[derive(Deserialize)]
+struct ProviderProfilesFile {
+    profiles: Vec<ProviderProfile>,
+}
+
+/// 服务商配置表，添加账号时取第一个匹配的配置
+/// - 用户配置排在内置配置前面，同名时替换内置配置
+#[derive(Clone, Debug)]
+pub struct ProviderRegistry {
+    profiles: Vec<ProviderProfile>,
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ProviderRegistry {
+    /// 不带任何配置
+    pub fn empty() -> Self {
+        Self { profiles: Vec::new() }
+    }
+
+    /// 内置配置
+    pub fn builtin() -> Self {
+        Self {
+            profiles: vec![
+                // 拒绝分片请求，拿 1 比特数据都要算下载了整个文件的流量；
+                // 免费账号 30 分钟内最多 600 个请求
+                ProviderProfile::new(
+                    "jianguoyun",
+                    &["https://dav.jianguoyun.com/"],
+                )
+                .with_disable_ranges(true)
+                .with_rate_limit(RateLimit::new(600, 30 * 60)),
+                ProviderProfile::new(
+                    "teracloud",
+                    &["teracloud.jp/", "infini-cloud.net/"],
+                ),
+                ProviderProfile::new(
+                    "nextcloud",
+                    &["/remote.php/dav/", "/remote.php/webdav/"],
+                )
+                .with_forbidden_chars(&['\\'])
+                .with_checksum_props(&[
+                    "{http://owncloud.org/ns}checksums",
+                ])
+                .with_oc_mtime_header(true),
+            ],
+        }
+    }
+
+    pub fn profiles(&self) -> &[ProviderProfile] {
+        &self.profiles
+    }
+
+    /// 添加用户配置，同名的旧配置会被替换
+    pub fn register(&mut self, profile: ProviderProfile) {
+        self.profiles.retain(|p| p.name != profile.name);
+        self.profiles.insert(0, profile);
+    }
+
+    /// 从 JSON 配置文件加载用户配置
+    /// - 文件里靠前的配置优先匹配
+    pub fn load_file(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), WebDavClientError> {
+        let text = std::fs::read_to_string(path)?;
+        let ProviderProfilesFile { profiles } =
+            serde_json::from_str(&text)
+                .map_err(WebDavClientError::SerdeJsonErr)?;
+
+        for profile in profiles.into_iter().rev() {
+            self.register(profile);
+        }
+        Ok(())
+    }
+
+    /// 查找适用于该账号地址的配置
+    pub fn match_url(&self, base_url: &str) -> Option<&ProviderProfile> {
+        self.profiles.iter().find(|profile| profile.matches(base_url))
+    }
+}
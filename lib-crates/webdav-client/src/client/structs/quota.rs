@@ -0,0 +1,68 @@
+use crate::client::structs::friendly_xml::format_size;
+use serde::Serialize;
+
+/// 账号或目录的配额（RFC 4331），各项取不到时为 `None`
+#[derive(Clone, Debug, Serialize)]
+pub struct QuotaInfo {
+    /// 查询的远端 URL
+    pub url: String,
+    pub used_bytes: Option<u64>,
+    pub available_bytes: Option<u64>,
+    /// 服务端声明没有上限（Nextcloud/ownCloud 返回 `-3`）
+    pub unlimited: bool,
+    /// 子目录没有返回配额属性，数据取自账号根目录
+    pub from_root: bool,
+}
+
+impl QuotaInfo {
+    /// 已用 + 可用
+    pub fn total_bytes(&self) -> Option<u64> {
+        Some(self.used_bytes? + self.available_bytes?)
+    }
+
+    /// 剩余空间是否放得下 `bytes`；没有上限或服务端没给可用空间时按放得下处理
+    pub fn can_store(&self, bytes: u64) -> bool {
+        self.unlimited
+            || self
+                .available_bytes
+                .is_none_or(|available| bytes <= available)
+    }
+}
+
+/// 配额的友好化输出
+#[derive(Clone, Debug, Serialize)]
+pub struct FriendlyQuota {
+    pub url: String,
+    pub used_bytes: Option<u64>,
+    pub used_str: Option<String>, // 格式化后的大小，比如 "12.3MB"
+    pub available_bytes: Option<u64>,
+    pub available_str: Option<String>,
+    pub total_bytes: Option<u64>,
+    pub total_str: Option<String>,
+    pub usage_percent: Option<f64>, // 已用占总量的百分比
+    pub unlimited: bool,
+}
+
+impl FriendlyQuota {
+    pub fn new(quota: &QuotaInfo) -> Self {
+        let total_bytes = quota.total_bytes();
+        let usage_percent = match (quota.used_bytes, total_bytes) {
+            (Some(used), Some(total)) if total > 0 => {
+                Some(used as f64 / total as f64 * 100.0)
+            }
+            _ => None,
+        };
+
+        Self {
+            url: quota.url.clone(),
+            used_bytes: quota.used_bytes,
+            used_str: format_size(quota.used_bytes),
+            available_bytes: quota.available_bytes,
+            available_str: format_size(quota.available_bytes),
+            total_bytes,
+            total_str: format_size(total_bytes),
+            usage_percent,
+            unlimited: quota.unlimited,
+        }
+    }
+}
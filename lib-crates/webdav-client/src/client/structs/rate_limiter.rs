@@ -0,0 +1,42 @@
+use crate::client::structs::provider_profile::RateLimit;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// 滑动窗口限速：窗口内请求数达到上限后，等最早的那个请求滑出窗口
+/// - 等待期间持有锁，排队的请求按顺序放行
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    max_requests: usize,
+    window: Duration,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            max_requests: (rate_limit.max_requests as usize).max(1),
+            window: Duration::from_secs(rate_limit.per_secs),
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 发请求前调用，超出频率时等待
+    pub async fn acquire(&self) {
+        let mut sent = self.sent.lock().await;
+
+        let now = Instant::now();
+        while sent.front().is_some_and(|at| now - *at >= self.window) {
+            sent.pop_front();
+        }
+
+        if sent.len() >= self.max_requests
+            && let Some(oldest) = sent.pop_front()
+        {
+            tokio::time::sleep_until(oldest + self.window).await;
+        }
+
+        sent.push_back(Instant::now());
+    }
+}
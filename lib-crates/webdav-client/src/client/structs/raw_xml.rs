@@ -1,3 +1,5 @@
+use crate::client::structs::http_date::parse_http_date;
+use crate::client::structs::propfind::TDeadProps;
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
@@ -32,7 +34,8 @@ pub struct PropStat {
 }
 
 /// 对应 `<D:prop>` 节点，列出资源的所有属性
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// - 解析时按命名空间匹配，见 [`MultiStatus::from_xml`]；除特别注明外都是 `DAV:` 下的属性
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct Prop {
     /// `<resourcetype>`：资源类型（文件/目录）
@@ -43,7 +46,7 @@ pub struct Prop {
     #[serde(rename = "getcontentlength")]
     pub content_length: Option<u64>,
 
-    /// `<getlastmodified>`：最后修改时间（HTTP-date 格式）
+    /// `<getlastmodified>`：最后修改时间（HTTP-date 格式，也兼容 RFC 850、asctime 和 ISO 8601）
     #[serde(rename = "getlastmodified", deserialize_with = "de_http_date", default)]
     pub last_modified: Option<DateTime<FixedOffset>>,
 
@@ -52,8 +55,8 @@ pub struct Prop {
     pub content_type: Option<String>,
 
     /// `<creationdate>`：资源创建时间（ISO8601，通常以 Z 结尾表示 UTC）
-    #[serde(rename = "creationdate")]
-    pub creation_date: Option<String>,
+    #[serde(rename = "creationdate", deserialize_with = "de_http_date", default)]
+    pub creation_date: Option<DateTime<FixedOffset>>,
 
     /// `<getetag>`：实体标签（文件内容的标识符，可用于缓存或变更检测）
     #[serde(rename = "getetag")]
@@ -69,21 +72,101 @@ pub struct Prop {
     /// `<current-user-privilege-set>`：当前用户对该资源的权限集合
     #[serde(rename = "current-user-privilege-set")]
     pub current_user_privilege_set: Option<CurrentUserPrivilegeSet>,
+
+    /// `<oc:checksums>`：Nextcloud/ownCloud 的校验和列表（`http://owncloud.org/ns`）
+    pub checksums: Option<Checksums>,
+
+    /// `<md5>`：部分服务商直接给出的 MD5（只有哈希值），不限命名空间
+    pub md5: Option<String>,
+
+    /// `<sha1>`：部分服务商直接给出的 SHA1（只有哈希值），不限命名空间
+    pub sha1: Option<String>,
+
+    /// `<quota-available-bytes>`：剩余可用空间（RFC 4331），保留原始文本
+    #[serde(rename = "quota-available-bytes")]
+    pub quota_available_bytes: Option<String>,
+
+    /// `<quota-used-bytes>`：已用空间（RFC 4331），保留原始文本
+    #[serde(rename = "quota-used-bytes")]
+    pub quota_used_bytes: Option<String>,
+
+    /// `<lockdiscovery>`：资源上当前的锁（RFC 4918 第 15.8 节）
+    #[serde(rename = "lockdiscovery")]
+    pub lock_discovery: Option<LockDiscovery>,
+
+    /// 上面没有对应字段的属性，按命名空间和本地名索引
+    /// - 其他命名空间里和上面同名的属性（如 `oc:owner`）也在这里
+    #[serde(skip_deserializing)]
+    pub dead_props: TDeadProps,
 }
 
-/// 将 HTTP-date 格式的时间解析为 `DateTime<FixedOffset>`
+/// 将时间解析为 `DateTime<FixedOffset>`，无法识别的格式按缺失处理
 fn de_http_date<'de, D>(deserializer: D) -> Result<Option<DateTime<FixedOffset>>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let s: Option<String> = Option::deserialize(deserializer)?;
-    if let Some(s) = s {
-        DateTime::parse_from_rfc2822(&s)
-            .map(Some)
-            .map_err(serde::de::Error::custom)
-    } else {
-        Ok(None)
-    }
+    Ok(s.as_deref().and_then(parse_http_date))
+}
+
+/// `<oc:checksums>` 节点
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Checksums {
+    /// 多个 `<oc:checksum>`，每个形如 `SHA1:abc... MD5:def...`
+    #[serde(rename = "checksum", default)]
+    pub checksums: Vec<String>,
+}
+
+/// `<lockdiscovery>` 节点
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockDiscovery {
+    /// 多个 `<activelock>`，共享锁可能同时有多个
+    #[serde(rename = "activelock", default)]
+    pub active_locks: Vec<ActiveLock>,
+}
+
+/// `<activelock>` 节点
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ActiveLock {
+    /// `<lockscope>`：独占或共享
+    #[serde(rename = "lockscope")]
+    pub lock_scope: Option<LockScopeElement>,
+    /// `<depth>`：`0` 或 `infinity`
+    pub depth: Option<String>,
+    /// `<owner>`：加锁时提交的所有者信息
+    pub owner: Option<LockOwner>,
+    /// `<timeout>`：如 `Second-3600`、`Infinite`
+    pub timeout: Option<String>,
+    /// `<locktoken>`：锁令牌，如 `opaquelocktoken:...`
+    #[serde(rename = "locktoken")]
+    pub lock_token: Option<HrefElement>,
+    /// `<lockroot>`：加锁的资源，深度锁时可能是上级目录
+    #[serde(rename = "lockroot")]
+    pub lock_root: Option<HrefElement>,
+}
+
+/// `<lockscope>` 节点
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LockScopeElement {
+    pub exclusive: Option<EmptyElement>,
+    pub shared: Option<EmptyElement>,
+}
+
+/// `<owner>` 节点，可能是纯文本也可能包一个 `<href>`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LockOwner {
+    pub href: Option<String>,
+    #[serde(rename = "$text")]
+    pub text: Option<String>,
+}
+
+/// 只包含一个 `<href>` 的节点，例如 `<locktoken>`、`<lockroot>`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HrefElement {
+    pub href: String,
 }
 
 /// `<resourcetype>` 节点
@@ -0,0 +1,57 @@
+use crate::client::structs::friendly_xml::FriendlyResource;
+use serde::Serialize;
+
+/// 遍历远端目录时实际使用的方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum TreeWalkMethod {
+    /// 一次 `Depth: infinity` PROPFIND 拿到整棵树
+    DepthInfinity,
+    /// 逐个目录 `Depth: 1` PROPFIND，同时最多若干个请求
+    Parallel,
+}
+
+/// 目录树里的一项（不含根目录自身）
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteEntry {
+    /// 完整 URL，目录以 `/` 结尾
+    pub url: String,
+    /// 相对根目录的路径，已 URL 解码，用 `/` 分隔，例如 `子目录/b.txt`
+    pub relative_path: String,
+    /// 资源信息，其中 `full_path` 已解析为完整 URL
+    pub resource: FriendlyResource,
+}
+
+impl RemoteEntry {
+    /// 所在层级，根目录的直接子项为 1
+    pub fn depth(&self) -> usize {
+        self.relative_path.split('/').count()
+    }
+}
+
+/// 远端目录树，条目按 `relative_path` 排序，目录总是排在它的子项前面
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteTree {
+    /// 根目录的完整 URL，以 `/` 结尾
+    pub root_url: String,
+    pub method: TreeWalkMethod,
+    pub entries: Vec<RemoteEntry>,
+}
+
+impl RemoteTree {
+    pub fn files(&self) -> impl Iterator<Item = &RemoteEntry> {
+        self.entries.iter().filter(|entry| !entry.resource.is_dir)
+    }
+
+    pub fn dirs(&self) -> impl Iterator<Item = &RemoteEntry> {
+        self.entries.iter().filter(|entry| entry.resource.is_dir)
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files().count()
+    }
+
+    /// 所有文件的大小之和，服务端没给大小的文件按 0 计
+    pub fn total_size(&self) -> u64 {
+        self.files().filter_map(|entry| entry.resource.size).sum()
+    }
+}
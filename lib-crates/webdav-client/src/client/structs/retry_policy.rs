@@ -0,0 +1,196 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::http_date::parse_http_date;
+use chrono::Utc;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, Response, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// 重试同一个请求不会产生额外副作用的方法（RFC 9110 第 9.2.2 节、RFC 4918）
+/// - MKCOL、MOVE、COPY、LOCK、UNLOCK、POST 重发可能得到不同的结果，不在其中
+const IDEMPOTENT_METHODS: [&str; 8] = [
+    "GET",
+    "HEAD",
+    "OPTIONS",
+    "PUT",
+    "DELETE",
+    "PROPFIND",
+    "PROPPATCH",
+    "SEARCH",
+];
+
+/// 请求失败时的重试策略，作用于账号发出的所有请求
+/// - 幂等方法：连接失败、超时、传输中断（响应体/IO 错误）以及 `retry_statuses` 里的状态码都会重试
+/// - 非幂等方法：只在请求确定没被处理时重试（连接没建立、429）
+/// - 流式请求体（上传文件）无法重发，不会重试
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// 最多发送的次数（含第一次），为 1 时不重试
+    pub max_attempts: u32,
+    /// 第一次重试前的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+    /// 单次等待的上限
+    pub max_delay: Duration,
+    /// 幂等请求遇到这些状态码时重试
+    pub retry_statuses: Vec<u16>,
+    /// 按响应的 `Retry-After` 等待
+    /// - 要求的等待超过 `max_delay` 时不再重试，直接返回该响应
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new_default_config()
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+    ) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay,
+            ..Self::new_default_config()
+        }
+    }
+
+    /// 最多 4 次，等待 0.5s、1s、2s 左右，单次最多等 60s；408/429/500/502/503/504 重试
+    pub fn new_default_config() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            retry_statuses: vec![408, 429, 500, 502, 503, 504],
+            respect_retry_after: true,
+        }
+    }
+
+    /// 不重试
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::new_default_config() }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_delays(
+        mut self,
+        base_delay: Duration,
+        max_delay: Duration,
+    ) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_retry_statuses(mut self, retry_statuses: &[u16]) -> Self {
+        self.retry_statuses = retry_statuses.to_vec();
+        self
+    }
+
+    pub fn with_respect_retry_after(
+        mut self,
+        respect_retry_after: bool,
+    ) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// 第 `attempt` 次发送失败后的等待时间：指数退避 + 抖动
+    /// - 在 `[d/2, d)` 之间随机，避免多个请求同时重试
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let half = exp / 2;
+        let jitter = RandomState::new().build_hasher().finish();
+        half + half.mul_f64((jitter % 1000) as f64 / 1000.0)
+    }
+
+    /// 收到响应后是否重试，返回等待时间
+    pub(crate) fn delay_for_response(
+        &self,
+        method: &Method,
+        response: &Response,
+        attempt: u32,
+    ) -> Option<Duration> {
+        let status = response.status();
+        let retryable = if is_idempotent(method) {
+            self.retry_statuses.contains(&status.as_u16())
+        } else {
+            status == StatusCode::TOO_MANY_REQUESTS
+        };
+        if !retryable {
+            return None;
+        }
+
+        match retry_after(response.headers()) {
+            Some(wait) if self.respect_retry_after => {
+                (wait <= self.max_delay).then_some(wait)
+            }
+            _ => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// 请求出错（没拿到响应）后是否重试，返回等待时间
+    pub(crate) fn delay_for_error(
+        &self,
+        method: &Method,
+        error: &WebDavClientError,
+        attempt: u32,
+    ) -> Option<Duration> {
+        let WebDavClientError::RequestErr(error) = error else {
+            return None;
+        };
+
+        let retryable = if is_idempotent(method) {
+            error.is_connect()
+                || error.is_timeout()
+                || error.is_body()
+                || is_io_error(error)
+        } else {
+            error.is_connect()
+        };
+
+        retryable.then(|| self.backoff(attempt))
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    IDEMPOTENT_METHODS.contains(&method.as_str())
+}
+
+/// 错误链里有没有 IO 错误（如连接被重置）
+/// - 构造请求、重定向策略这类错误每次都会一样失败，不在此列
+fn is_io_error(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        if e.is::<std::io::Error>() {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// 解析 `Retry-After`：秒数或 HTTP-date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = parse_http_date(value)?;
+    Some(
+        (at.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default(),
+    )
+}
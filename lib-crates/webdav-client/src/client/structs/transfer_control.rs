@@ -0,0 +1,105 @@
+use crate::client::error::WebDavClientError;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// 传输的运行状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferState {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+/// 传输控制句柄，可以随时暂停、继续、取消正在进行的上传/下载
+/// - clone 后控制的是同一次传输，调用方留一份，另一份放进 `DownloadConfig`/`UploadConfig`
+/// - 控制是协作式的：传输在每收到（或发送）一块数据、每开始一个文件前检查状态
+/// - 暂停期间连接保持不动，暂停太久可能被服务端断开，这时文件记为失败，`.part` 保留，重新下载会续传
+/// - 取消后不能再继续，已取消的句柄不要复用
+#[derive(Clone, Debug)]
+pub struct TransferControl {
+    state: Arc<watch::Sender<TransferState>>,
+    keep_partial_on_cancel: bool,
+}
+
+impl Default for TransferControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferControl {
+    /// 默认取消时保留 `.part` 和续传记录，下次可以接着下载
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(TransferState::Running)),
+            keep_partial_on_cancel: true,
+        }
+    }
+
+    /// 设置取消时是否保留未完成的 `.part` 文件，为 `false` 时取消会删掉它们
+    pub fn keep_partial_on_cancel(mut self, keep: bool) -> Self {
+        self.keep_partial_on_cancel = keep;
+        self
+    }
+
+    pub fn keeps_partial_on_cancel(&self) -> bool {
+        self.keep_partial_on_cancel
+    }
+
+    pub fn state(&self) -> TransferState {
+        *self.state.borrow()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state() == TransferState::Cancelled
+    }
+
+    /// 暂停，已取消时无效
+    pub fn pause(&self) {
+        self.state.send_if_modified(|state| {
+            if *state == TransferState::Running {
+                *state = TransferState::Paused;
+                return true;
+            }
+            false
+        });
+    }
+
+    /// 继续，已取消时无效
+    pub fn resume(&self) {
+        self.state.send_if_modified(|state| {
+            if *state == TransferState::Paused {
+                *state = TransferState::Running;
+                return true;
+            }
+            false
+        });
+    }
+
+    /// 取消，暂停中的传输也会立刻醒来并退出
+    pub fn cancel(&self) {
+        self.state.send_replace(TransferState::Cancelled);
+    }
+
+    /// 检查点：运行中直接返回，暂停时等到继续，取消时返回 [`WebDavClientError::Cancelled`]
+    pub async fn checkpoint(&self) -> Result<(), WebDavClientError> {
+        if self.state() == TransferState::Running {
+            return Ok(());
+        }
+
+        let mut receiver = self.state.subscribe();
+        loop {
+            match *receiver.borrow_and_update() {
+                TransferState::Running => return Ok(()),
+                TransferState::Cancelled => {
+                    return Err(WebDavClientError::Cancelled);
+                }
+                TransferState::Paused => {}
+            }
+            // 发送端就在 self 里，不会提前关闭
+            if receiver.changed().await.is_err() {
+                return Err(WebDavClientError::Cancelled);
+            }
+        }
+    }
+}
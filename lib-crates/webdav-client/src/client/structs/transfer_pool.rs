@@ -0,0 +1,220 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use futures_util::FutureExt;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 传输并发上限，作用于同一个 `WebDavClient` 发起的所有上传/下载
+#[derive(Clone, Copy, Debug)]
+pub struct TransferLimits {
+    /// 全局同时进行的传输数
+    pub global: usize,
+    /// 同一个主机同时进行的传输数（多个账号可能在同一个服务商）
+    pub per_host: usize,
+    /// 同一个账号同时进行的传输数
+    pub per_account: usize,
+}
+
+impl TransferLimits {
+    pub fn new(
+        global: usize,
+        per_host: usize,
+        per_account: usize,
+    ) -> Self {
+        Self { global, per_host, per_account }
+    }
+}
+
+impl Default for TransferLimits {
+    fn default() -> Self {
+        Self { global: 32, per_host: 8, per_account: 8 }
+    }
+}
+
+/// 一次传输占用的许可，drop 时归还
+pub struct TransferPermit {
+    _account: OwnedSemaphorePermit,
+    _host: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+/// 传输池：按全局、主机、账号三级信号量限制并发
+/// - 内部都是 `Arc`，clone 后共享同一组限制，可以 move 进 `tokio::spawn`
+#[derive(Clone)]
+pub struct TransferPool {
+    limits: TransferLimits,
+    global: Arc<Semaphore>,
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    accounts: Arc<Mutex<HashMap<WebDavChildClientKey, Arc<Semaphore>>>>,
+}
+
+fn semaphore_of<K>(
+    map: &Mutex<HashMap<K, Arc<Semaphore>>>,
+    key: K,
+    permits: usize,
+) -> Arc<Semaphore>
+where
+    K: std::hash::Hash + Eq,
+{
+    let mut map = map.lock().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(
+        map.entry(key)
+            .or_insert_with(|| Arc::new(Semaphore::new(permits.max(1)))),
+    )
+}
+
+impl TransferPool {
+    pub fn new(limits: TransferLimits) -> Self {
+        Self {
+            limits,
+            global: Arc::new(Semaphore::new(limits.global.max(1))),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            accounts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn limits(&self) -> TransferLimits {
+        self.limits
+    }
+
+    /// 账号和主机对应的信号量，第一次用到时创建
+    fn semaphores(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> (Arc<Semaphore>, Arc<Semaphore>) {
+        let host = Url::parse(&web_dav_child_client_key.get_base_url())
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let account = semaphore_of(
+            &self.accounts,
+            web_dav_child_client_key.clone(),
+            self.limits.per_account,
+        );
+        let host = semaphore_of(&self.hosts, host, self.limits.per_host);
+        (account, host)
+    }
+
+    /// 等待账号、主机、全局三级许可都拿到后返回
+    /// - 固定按 账号 -> 主机 -> 全局 的顺序获取，避免互相等待
+    pub async fn acquire(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<TransferPermit, WebDavClientError> {
+        let (account, host) = self.semaphores(web_dav_child_client_key);
+
+        let closed =
+            |_| WebDavClientError::String("传输池已关闭".to_string());
+
+        let _account = account.acquire_owned().await.map_err(closed)?;
+        let _host = host.acquire_owned().await.map_err(closed)?;
+        let _global = Arc::clone(&self.global)
+            .acquire_owned()
+            .await
+            .map_err(closed)?;
+
+        Ok(TransferPermit { _account, _host, _global })
+    }
+
+    /// 不等待，三级许可都有空闲时才拿到，否则返回 `None`（已拿到的随即归还）
+    /// - 已经持有许可的任务想多开连接（如并发分片）时用，等待可能和其他任务互相卡住
+    pub fn try_acquire(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Option<TransferPermit> {
+        let (account, host) = self.semaphores(web_dav_child_client_key);
+
+        let _account = account.try_acquire_owned().ok()?;
+        let _host = host.try_acquire_owned().ok()?;
+        let _global = Arc::clone(&self.global).try_acquire_owned().ok()?;
+
+        Some(TransferPermit { _account, _host, _global })
+    }
+
+    /// 拿到许可后把任务 spawn 到 tokio 运行时上，任务结束时归还许可
+    /// - 返回的 future 只负责等待结果，丢弃它不会取消任务
+    /// - 任务 panic 时转成 [`WebDavClientError::String`]
+    pub async fn spawn<F>(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        task: F,
+    ) -> Result<
+        impl Future<Output = Result<F::Output, WebDavClientError>>
+        + Send
+        + 'static,
+        WebDavClientError,
+    >
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let permit = self.acquire(web_dav_child_client_key).await?;
+
+        let handle = tokio::spawn(async move {
+            let _permit = permit;
+            task.await
+        });
+
+        Ok(handle.map(|result| {
+            result.map_err(|e| {
+                WebDavClientError::String(format!("传输任务异常退出: {e}"))
+            })
+        }))
+    }
+
+    /// 绑定到一个账号，之后申请许可、spawn 任务不用再传账号
+    pub fn for_account(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> AccountPool {
+        AccountPool {
+            transfer_pool: self.clone(),
+            web_dav_child_client_key: web_dav_child_client_key.clone(),
+        }
+    }
+}
+
+/// 绑定到某个账号的传输池
+/// - clone 后共享同一组限制，可以 move 进任务里继续派发子任务（如目录下的文件）
+#[derive(Clone)]
+pub struct AccountPool {
+    transfer_pool: TransferPool,
+    web_dav_child_client_key: WebDavChildClientKey,
+}
+
+impl AccountPool {
+    /// 见 [`TransferPool::acquire`]
+    pub async fn acquire(
+        &self,
+    ) -> Result<TransferPermit, WebDavClientError> {
+        self.transfer_pool.acquire(&self.web_dav_child_client_key).await
+    }
+
+    /// 见 [`TransferPool::try_acquire`]
+    pub fn try_acquire(&self) -> Option<TransferPermit> {
+        self.transfer_pool.try_acquire(&self.web_dav_child_client_key)
+    }
+
+    /// 见 [`TransferPool::spawn`]
+    pub async fn spawn<F>(
+        &self,
+        task: F,
+    ) -> Result<
+        impl Future<Output = Result<F::Output, WebDavClientError>>
+        + Send
+        + 'static,
+        WebDavClientError,
+    >
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.transfer_pool
+            .spawn(&self.web_dav_child_client_key, task)
+            .await
+    }
+}
@@ -1,8 +1,11 @@
 use crate::client::TWebDavChildClientValue;
 use crate::client::error::WebDavClientError;
-use base64::Engine;
-use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
-use reqwest::{Client, Url};
+use crate::client::structs::auth::AuthStrategy;
+use crate::client::structs::capabilities::TCapabilitiesCache;
+use crate::client::structs::http_client::WebDavHttpClient;
+use crate::client::structs::provider_profile::ProviderProfile;
+use crate::client::structs::retry_policy::RetryPolicy;
+use reqwest::Url;
 use sha2::{Digest, Sha256};
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
@@ -27,28 +30,6 @@ fn format_url(url: &str) -> Result<Url, WebDavClientError> {
     Ok(base_url)
 }
 
-pub(crate) fn build_client_with_auth(
-    username: &str,
-    password: &str,
-) -> Result<Client, WebDavClientError> {
-    let mut headers = HeaderMap::new();
-
-    let token = base64::engine::general_purpose::STANDARD
-        .encode(format!("{username}:{password}"));
-
-    let auth_val = HeaderValue::from_str(&format!("Basic {token}"))
-        .map_err(|e| {
-            WebDavClientError::InvalidHeaderValue(e.to_string())
-        })?;
-
-    headers.insert(AUTHORIZATION, auth_val);
-
-    let client =
-        Client::builder().http1_only().default_headers(headers).build()?;
-
-    Ok(client)
-}
-
 impl WebDavChildClientKey {
     pub fn new(
         base_url: &str,
@@ -83,7 +64,10 @@ pub type EncryptedUsername = String;
 
 pub struct WebDavChildClientValue {
     base_url: Url,
-    pub(crate) client: Client,
+    pub(crate) client: WebDavHttpClient,
+    auth_scheme: &'static str,
+    /// 服务端能力的缓存，由 `Capabilities` 填充
+    pub(crate) capabilities: TCapabilitiesCache,
     encrypted_username: EncryptedUsername,
     encrypted_password: EncryptedPassword,
 }
@@ -113,16 +97,35 @@ impl WebDavChildClientValue {
         username: &str,
         password: &str,
     ) -> Result<Self, WebDavClientError> {
-        let base_url = format_url(base_url)?;
+        Self::with_auth(
+            base_url,
+            username,
+            AuthStrategy::basic(username, password),
+            None,
+        )
+    }
 
-        let client = build_client_with_auth(username, password)?;
+    /// 指定认证方式和服务商配置
+    /// - `username` 只用来区分账号（令牌类认证可以填一个便于识别的名字）
+    pub fn with_auth(
+        base_url: &str,
+        username: &str,
+        auth: AuthStrategy,
+        profile: Option<ProviderProfile>,
+    ) -> Result<Self, WebDavClientError> {
+        let base_url = format_url(base_url)?;
 
         let (encrypted_username, encrypted_password) =
-            encrypted_account(username, password);
+            encrypted_account(username, auth.secret());
+        let auth_scheme = auth.scheme();
+
+        let client = WebDavHttpClient::new(auth, profile)?;
 
         Ok(Self {
             base_url,
             client,
+            auth_scheme,
+            capabilities: TCapabilitiesCache::default(),
             encrypted_username, // sha-256加密
             encrypted_password,
         })
@@ -132,6 +135,31 @@ impl WebDavChildClientValue {
         self.base_url.to_owned()
     }
 
+    /// 认证方式的名字，例如 `Basic`、`Digest`
+    pub fn get_auth_scheme(&self) -> &'static str {
+        self.auth_scheme
+    }
+
+    /// 匹配到的服务商配置
+    pub fn get_profile(&self) -> Option<&ProviderProfile> {
+        self.client.profile()
+    }
+
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        self.client.retry_policy()
+    }
+
+    /// 设置账号的重试策略
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.set_retry_policy(retry_policy);
+        self
+    }
+
+    /// 修改账号的重试策略，正在进行的请求继续使用旧策略
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.client.set_retry_policy(retry_policy);
+    }
+
     #[cfg(feature = "show-test-detail")]
     pub(crate) fn get_encrypted_username(&self) -> String {
         self.encrypted_username.to_owned()
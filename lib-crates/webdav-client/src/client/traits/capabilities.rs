@@ -0,0 +1,21 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::capabilities::ServerCapabilities;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Capabilities {
+    /// 获取账号所在服务端的能力
+    /// - 添加账号时会在后台探测一次，之后直接读缓存
+    /// - 缓存为空（后台探测还没完成或失败）时在这里探测
+    async fn get_capabilities(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<ServerCapabilities, WebDavClientError>;
+
+    /// 重新探测并更新缓存，服务端配置变化后使用
+    async fn refresh_capabilities(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<ServerCapabilities, WebDavClientError>;
+}
@@ -1,6 +1,9 @@
 use crate::client::error::WebDavClientError;
+use crate::client::structs::progress::TProgressSender;
+use crate::client::structs::transfer_control::TransferControl;
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
 use async_trait::async_trait;
+use std::time::Duration;
 
 pub enum ThreadMode {
     Auto,
@@ -8,30 +11,188 @@ pub enum ThreadMode {
     MultipleThread,
 }
 
+/// 默认分片大小
+pub const DEFAULT_SEGMENT_SIZE: u64 = 4 * 1024 * 1024;
+/// 默认单文件并发分片数
+pub const DEFAULT_SEGMENT_CONCURRENCY: usize = 4;
+/// 默认单次调用同时下载的文件数
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
 pub struct DownloadConfig {
     /// 线程模式
     pub thread_mode: ThreadMode,
     /// 自动分片
     pub auto_segment_file: bool,
+    /// 分片大小（字节），只在 `auto_segment_file` 为 `true` 时生效
+    pub segment_size: u64,
+    /// 单个文件同时请求的分片数
+    /// - 为 1 时逐片顺序下载
+    /// - 大于 1 时先预分配文件，再把多个分片并发写到各自的位置
+    /// - 多出来的分片各占一个传输池许可，许可不够时少开分片
+    /// - `ThreadMode::SingleThread` 下总是按 1 处理
+    pub segment_concurrency: usize,
+    /// 本次调用同时下载的文件数，只在多线程模式下生效
+    /// - 另外还受 `WebDavClient` 传输池的全局/主机/账号上限约束
+    /// - 也是遍历目录时同时发出的 PROPFIND 数（服务端不允许 `Depth: infinity` 时）
+    /// - 也是单个目录里同时下载的文件数
+    pub max_concurrency: usize,
+    /// 进度事件的发送端，为 `None` 时不汇报进度
+    /// - 用 [`progress_channel`](crate::client::structs::progress::progress_channel) 创建
+    pub progress: Option<TProgressSender>,
+    /// 暂停/继续/取消的控制句柄，为 `None` 时无法中途停止
+    pub control: Option<TransferControl>,
+    /// 本地已有同名、同大小的文件（且没有未完成的 `.part`）时跳过
+    pub skip_existing: bool,
+    /// 下载完成后按服务端给出的校验和（`oc:checksums`、`OC-Checksum` 等）校验文件
+    /// - 需要重新读一遍文件，大文件会多花一些时间
+    /// - 分片范围和文件大小总是会校验，不受这个开关影响
+    pub verify_checksum: bool,
+    /// 下载完成后把本地文件的修改时间设为远端的 `getlastmodified`
+    /// - 远端没有返回修改时间时保持本地当前时间
+    pub preserve_mtime: bool,
 }
 
 impl DownloadConfig {
     pub fn new(thread_mode: ThreadMode, auto_segment_file: bool) -> Self {
-        Self { thread_mode, auto_segment_file }
+        Self {
+            thread_mode,
+            auto_segment_file,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            segment_concurrency: DEFAULT_SEGMENT_CONCURRENCY,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            progress: None,
+            control: None,
+            skip_existing: false,
+            verify_checksum: true,
+            preserve_mtime: true,
+        }
     }
 
     pub fn new_default_config() -> Self {
-        Self { thread_mode: ThreadMode::Auto, auto_segment_file: true }
+        Self::new(ThreadMode::Auto, true)
+    }
+
+    /// 设置分片大小和单文件并发分片数
+    pub fn with_segments(
+        mut self,
+        segment_size: u64,
+        segment_concurrency: usize,
+    ) -> Self {
+        self.segment_size = segment_size;
+        self.segment_concurrency = segment_concurrency;
+        self
+    }
+
+    /// 设置本次调用同时下载的文件数
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// 设置进度事件的发送端
+    pub fn with_progress(mut self, progress: TProgressSender) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 设置控制句柄，调用方保留它的 clone 来暂停/继续/取消本次下载
+    pub fn with_control(mut self, control: TransferControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// 设置是否跳过本地已存在的完整文件
+    pub fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// 设置下载完成后是否校验校验和
+    pub fn with_verify_checksum(mut self, verify_checksum: bool) -> Self {
+        self.verify_checksum = verify_checksum;
+        self
+    }
+
+    /// 设置是否保留远端的修改时间
+    pub fn with_preserve_mtime(mut self, preserve_mtime: bool) -> Self {
+        self.preserve_mtime = preserve_mtime;
+        self
+    }
+}
+
+/// 单个请求路径的下载结果
+#[derive(Debug)]
+pub enum DownloadOutcome {
+    /// 已下载；目录表示其中的文件都已下载（或跳过）
+    Downloaded,
+    /// 本地已有完整文件，按 `skip_existing` 跳过
+    Skipped,
+    /// 失败；目录里有文件失败时其余文件照常下载，全部结束后记为失败
+    Failed(WebDavClientError),
+}
+
+/// `download_files` 里每个请求路径各对应一条
+#[derive(Debug)]
+pub struct DownloadFileReport {
+    /// 调用时传入的远端路径，原样保留，方便重试
+    pub path: String,
+    /// 本地目标路径，获取远端信息就失败时为 `None`
+    pub local_path: Option<String>,
+    /// 是否目录
+    pub is_dir: bool,
+    /// 已落盘的字节数，目录为其中所有文件之和，跳过的文件不计
+    pub bytes: u64,
+    /// 从开始处理到结束的耗时（不含排队等待传输池许可的时间）
+    pub elapsed: Duration,
+    /// 下载结果
+    pub outcome: DownloadOutcome,
+}
+
+impl DownloadFileReport {
+    pub fn is_ok(&self) -> bool {
+        !matches!(self.outcome, DownloadOutcome::Failed(_))
+    }
+}
+
+/// 一次 `download_files` 调用的完整结果，按请求路径的顺序排列
+#[derive(Debug, Default)]
+pub struct DownloadReport {
+    pub files: Vec<DownloadFileReport>,
+}
+
+impl DownloadReport {
+    /// 成功（含跳过）的条目数
+    pub fn success_count(&self) -> usize {
+        self.files.iter().filter(|f| f.is_ok()).count()
+    }
+
+    /// 失败的条目
+    pub fn failed(&self) -> Vec<&DownloadFileReport> {
+        self.files.iter().filter(|f| !f.is_ok()).collect()
+    }
+
+    /// 失败条目的远端路径，可以直接传回 `download_files` 重试
+    pub fn failed_paths(&self) -> Vec<String> {
+        self.failed().into_iter().map(|f| f.path.clone()).collect()
+    }
+
+    /// 所有条目落盘的字节数之和
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.bytes).sum()
     }
 }
 
 #[async_trait]
 pub trait Download {
+    /// 把远端文件或目录下载到本地 `output_path` 下
+    ///
+    /// - 单个路径失败不会中断整体，结果记录在 [`DownloadReport`] 里
+    /// - 只有账号不存在这类整体性错误才会返回 `Err`
     async fn download_files(
         &self,
         web_dav_child_client_key: &WebDavChildClientKey,
         files_path: Vec<String>,
         output_path: &str,
         download_config: Option<DownloadConfig>,
-    ) -> Result<String, WebDavClientError>;
+    ) -> Result<DownloadReport, WebDavClientError>;
 }
@@ -1,37 +1,68 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+
+/// 远端文件/目录的增删改操作
+///
+/// 所有路径都和 [`Folder`](crate::client::traits::folder::Folder) 一样相对于账号的 `base_url`，
+/// 包括 MOVE/COPY 的 `Destination` 在内都会经过
+/// [`format_url_path`](crate::client::traits::url_trait::UrlParse::format_url_path) 校验，
+/// 不可能越过 `base_url` 操作别的目录。
+#[async_trait]
 pub trait FileControl {
-    /// 创建目录
-    fn mkdir(
+    /// 创建目录（MKCOL）
+    /// - 在 `parent_path` 下创建 `dir_name`，目录已存在时视为成功
+    async fn mkdir(
         &self,
-        file_path: &str,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        parent_path: &str,
         dir_name: &str,
-    ) -> impl Future<Output = String> + Send;
+    ) -> Result<(), WebDavClientError>;
 
-    /// 删除文件
-    fn rm_file(
+    /// 删除文件（DELETE）
+    /// - `force` 为 `true` 时文件本来就不存在也视为成功
+    async fn rm_file(
         &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
         file_path: &str,
         force: bool,
-    ) -> impl Future<Output = String> + Send;
+    ) -> Result<(), WebDavClientError>;
 
-    /// 删除目录
-    fn rmdir(
+    /// 删除目录（DELETE）
+    /// - `force` 为 `false` 时只允许删除空目录
+    /// - `force` 为 `true` 时连同子项一起删除，目录不存在也视为成功
+    async fn rmdir(
         &self,
-        file_path: &str,
-        dir_name: &str,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        dir_path: &str,
         force: bool,
-    ) -> impl Future<Output = String> + Send;
+    ) -> Result<(), WebDavClientError>;
 
-    /// 重命名（文件或目录）
-    fn rename(
+    /// 重命名（文件或目录），只改名字不换目录，目标已存在时失败
+    async fn rename(
         &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
         file_path: &str,
         new_name: &str,
-    ) -> impl Future<Output = String> + Send;
+    ) -> Result<(), WebDavClientError>;
+
+    /// 移动（可跨目录，MOVE）
+    /// - `overwrite` 对应 `Overwrite: T/F` 请求头
+    async fn move_item(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        from_path: &str,
+        to_path: &str,
+        overwrite: bool,
+    ) -> Result<(), WebDavClientError>;
 
-    /// 移动（可跨目录）
-    fn move_item(
+    /// 服务端复制（COPY），目录会连同子项一起复制
+    /// - `overwrite` 对应 `Overwrite: T/F` 请求头
+    async fn copy_item(
         &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
         from_path: &str,
         to_path: &str,
-    ) -> impl Future<Output = String> + Send;
+        overwrite: bool,
+    ) -> Result<(), WebDavClientError>;
 }
@@ -1,11 +1,17 @@
 use crate::client::enums::client_enum::Depth;
 use crate::client::error::WebDavClientError;
-use crate::client::structs::raw_xml::MultiStatus;
+use crate::client::structs::propfind::{
+    PropName, PropfindRequest, TDeadProps,
+};
+use crate::client::structs::raw_xml::{MultiStatus, Response};
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
 
 pub type TFileMetas = Vec<Result<MultiStatus, WebDavClientError>>;
 pub type TFolders = Vec<Result<MultiStatus, WebDavClientError>>;
+pub type TResponseStream =
+    BoxStream<'static, Result<Response, WebDavClientError>>;
 
 #[async_trait]
 pub trait Folder {
@@ -55,6 +61,38 @@ pub trait Folder {
         depth: &Depth,
     ) -> Result<MultiStatus, WebDavClientError>;
 
+    /// 和 [`Folder::get_folders`] 一样发送 PROPFIND，但边收边解析，逐个返回 `<response>`
+    /// - 内存占用和单个 `<response>` 相当，适合几十万项的大目录
+    /// - 请求失败、状态码异常时直接返回 `Err`；响应体读到一半出错时，流里返回一条 `Err` 后结束
+    async fn get_folders_stream(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        depth: &Depth,
+    ) -> Result<TResponseStream, WebDavClientError>;
+
+    /// 按 [`PropfindRequest`] 只请求指定的属性，大目录上比 `allprop` 快得多
+    /// - 配额、校验和、锁等属性有的服务端只在显式请求时返回
+    /// - `Prop` 里没有对应字段的属性放在 `Prop::dead_props`，按命名空间和本地名索引
+    /// - 服务端没有的属性会出现在 404 的 `propstat` 里
+    async fn propfind(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        depth: &Depth,
+        propfind_request: &PropfindRequest,
+    ) -> Result<MultiStatus, WebDavClientError>;
+
+    /// 读取单个资源上的自定义属性，写入见 [`Properties::proppatch`](crate::client::traits::properties::Properties::proppatch)
+    /// - 结果按命名空间和本地名索引，服务端没有的属性不出现在结果里
+    /// - 只能读 `Prop` 没有对应字段的属性，`DAV:` 下的常用属性请用 [`Folder::propfind`]
+    async fn get_custom_props(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        prop_names: &[PropName],
+    ) -> Result<TDeadProps, WebDavClientError>;
+
     async fn collect_folders(
         &self,
         key: &WebDavChildClientKey,
@@ -0,0 +1,50 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::lock::{LockInfo, LockOptions};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// WebDAV 锁（RFC 4918 第 6、9.10、9.11 节）
+///
+/// 拿到的锁记在账号上，之后这个账号对锁住范围内的 PUT/DELETE/MOVE/COPY/MKCOL
+/// 都会自动带上 `If` 头提交令牌，不需要手动处理。
+#[async_trait]
+pub trait Lock {
+    /// 加锁（LOCK），`lock_options` 为 `None` 时用 [`LockOptions::new_default_config`]
+    /// - 资源已被别人锁住时服务端返回 423，这里返回错误
+    /// - 对不存在的路径加锁时，服务端会创建一个空文件
+    async fn lock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        lock_options: Option<LockOptions>,
+    ) -> Result<LockInfo, WebDavClientError>;
+
+    /// 刷新账号在该路径上持有的锁，`timeout` 为 `None` 表示申请不限时
+    async fn refresh_lock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        timeout: Option<Duration>,
+    ) -> Result<LockInfo, WebDavClientError>;
+
+    /// 释放账号在该路径上持有的锁（UNLOCK）
+    async fn unlock(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<(), WebDavClientError>;
+
+    /// 账号当前持有、还没过期的锁
+    async fn held_locks(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+    ) -> Result<Vec<LockInfo>, WebDavClientError>;
+
+    /// 通过 `lockdiscovery` 查询资源上的所有锁（包括别人加的）
+    async fn discover_locks(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<Vec<LockInfo>, WebDavClientError>;
+}
@@ -1,7 +1,12 @@
+pub mod capabilities;
 pub mod download;
 pub mod file_control;
 pub mod folder;
+pub mod lock;
+pub mod properties;
+pub mod quota;
 pub mod search;
+pub mod tree_walk;
 pub mod upload;
 pub mod url_trait;
 pub mod safe_atomic_ops;
@@ -0,0 +1,22 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::proppatch::{
+    PropPatchRequest, PropPatchResult,
+};
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+
+/// 自定义属性（死属性）的读写，适合在远端资源上保存同步元数据
+/// - 读取见 [`Folder::get_custom_props`](crate::client::traits::folder::Folder::get_custom_props)
+#[async_trait]
+pub trait Properties {
+    /// 通过 PROPPATCH 设置/删除属性
+    /// - 请求本身失败（404、423 等）时返回错误
+    /// - 单个属性被拒绝时不算错误，看 [`PropPatchResult::failed`]；
+    ///   这时同一请求里的其他修改也不会生效
+    async fn proppatch(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        proppatch_request: &PropPatchRequest,
+    ) -> Result<PropPatchResult, WebDavClientError>;
+}
@@ -0,0 +1,18 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::quota::QuotaInfo;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Quota {
+    /// 查询账号根目录或子目录的配额
+    /// - 优先读 RFC 4331 的 `quota-used-bytes`/`quota-available-bytes`
+    /// - 子目录没有返回配额属性时退回账号根目录
+    /// - 没有 `quota-used-bytes` 时用 Nextcloud/ownCloud 的 `oc:size`
+    /// - 都取不到时返回错误
+    async fn get_quota(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+    ) -> Result<QuotaInfo, WebDavClientError>;
+}
@@ -1,4 +1,5 @@
 use crate::client::error::WebDavClientError;
+use crate::client::structs::auth::AuthStrategy;
 use crate::client::structs::webdav_child_client::WebDavChildClientKey;
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -13,6 +14,14 @@ pub trait SafeAtomicOps {
         password: &str,
     ) -> Result<WebDavChildClientKey, WebDavClientError>;
 
+    /// 按指定的认证方式添加账号，`add_account` 相当于使用 `AuthStrategy::Basic`
+    fn add_account_with_auth(
+        &mut self,
+        base_url: &str,
+        username: &str,
+        auth: AuthStrategy,
+    ) -> Result<WebDavChildClientKey, WebDavClientError>;
+
     fn can_modify_value<T>(arc_client: &Arc<RwLock<T>>) -> bool {
         let strong = Arc::strong_count(&arc_client);
         println!("Arc strong_count = {}", strong);
@@ -22,7 +31,7 @@ pub trait SafeAtomicOps {
         arc_client.try_write().is_ok()
     }
 
-   async fn remove_account(
+    async fn remove_account(
         &mut self,
         base_url: &str,
         username: &str,
@@ -1,6 +1,66 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset};
+
+/// 搜索条件，所有条件之间是"且"的关系，`None` 表示不限制
+pub struct SearchQuery {
+    /// 文件名关键字（displayname 模糊匹配，不区分大小写）
+    pub keyword: String,
+    /// MIME 类型前缀，比如 `image/`、`application/pdf`
+    pub content_type: Option<String>,
+    /// 最小文件大小（字节，包含）
+    pub min_size: Option<u64>,
+    /// 最大文件大小（字节，包含）
+    pub max_size: Option<u64>,
+    /// 最后修改时间下限（包含）
+    pub modified_after: Option<DateTime<FixedOffset>>,
+    /// 最后修改时间上限（包含）
+    pub modified_before: Option<DateTime<FixedOffset>>,
+}
+
+impl SearchQuery {
+    /// 只按文件名关键字搜索
+    pub fn new(keyword: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            content_type: None,
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            modified_before: None,
+        }
+    }
+}
+
+pub struct SearchConfig {
+    /// 服务端不支持 SEARCH 时，回退遍历目录树的最大并发请求数
+    pub max_concurrency: usize,
+}
+
+impl SearchConfig {
+    pub fn new(max_concurrency: usize) -> Self {
+        Self { max_concurrency }
+    }
+
+    pub fn new_default_config() -> Self {
+        Self { max_concurrency: 4 }
+    }
+}
+
+#[async_trait]
 pub trait SearchFile {
-    fn search(
+    /// 在 `path` 目录下递归搜索满足 `query` 的文件和目录
+    ///
+    /// - 服务端在 `DASL` 响应头里声明了 `DAV:basicsearch` 时，直接发送 RFC 5323 `SEARCH`
+    /// - 否则回退为遍历目录树（见 [`TreeWalk::walk_tree`](crate::client::traits::tree_walk::TreeWalk::walk_tree)）后在客户端筛选，并发数由 [`SearchConfig`] 控制
+    /// - 返回的 [`FriendlyResource::full_path`] 是服务端给出的 href，回退搜索时是解析后的完整 URL
+    async fn search(
         &self,
-        keyword: &str,
-    ) -> impl Future<Output = Vec<String>> + Send;
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        query: &SearchQuery,
+        search_config: Option<SearchConfig>,
+    ) -> Result<Vec<FriendlyResource>, WebDavClientError>;
 }
@@ -0,0 +1,92 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::friendly_xml::FriendlyResource;
+use crate::client::structs::remote_tree::RemoteTree;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+
+/// 逐层遍历时默认同时发出的 PROPFIND 数
+pub const DEFAULT_WALK_CONCURRENCY: usize = 4;
+
+pub type TResourceStream<'a> =
+    BoxStream<'a, Result<FriendlyResource, WebDavClientError>>;
+
+/// 流式遍历的范围和过滤条件
+/// - 模式支持 `*`（不跨目录）、`**`（可跨目录）、`?`（单个字符），区分大小写
+/// - 不含 `/` 的模式匹配文件名，例如 `*.txt`；含 `/` 的匹配相对路径，例如 `照片/**/*.jpg`
+#[derive(Clone, Debug)]
+pub struct WalkConfig {
+    /// 最多往下几层，根目录的直接子项为 1，`None` 不限制
+    pub max_depth: Option<usize>,
+    /// 只返回匹配其中任一模式的项，为空时不限制
+    /// - 只影响返回哪些项，不匹配的目录仍然会进入
+    pub include: Vec<String>,
+    /// 匹配其中任一模式的项不返回，目录也不再进入
+    pub exclude: Vec<String>,
+    /// 是否返回目录，为 `false` 时只返回文件
+    pub include_dirs: bool,
+}
+
+impl Default for WalkConfig {
+    fn default() -> Self {
+        Self::new_default_config()
+    }
+}
+
+impl WalkConfig {
+    /// 不限层级，不过滤，返回文件和目录
+    pub fn new_default_config() -> Self {
+        Self {
+            max_depth: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_dirs: true,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    pub fn with_exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    pub fn with_include_dirs(mut self, include_dirs: bool) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+}
+
+#[async_trait]
+pub trait TreeWalk {
+    /// 列出目录下的整棵树（不含目录自身）
+    /// - 服务端接受时用一次 `Depth: infinity` PROPFIND；被拒绝或结果没有展开时，
+    ///   退回逐个目录 `Depth: 1`，同时最多 `max_concurrency` 个请求
+    /// - `Depth: infinity` 是否可用会记到账号的能力缓存里，之后不再重复尝试
+    /// - `max_concurrency` 为 `None` 时用 [`DEFAULT_WALK_CONCURRENCY`]
+    async fn walk_tree(
+        &self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        max_concurrency: Option<usize>,
+    ) -> Result<RemoteTree, WebDavClientError>;
+
+    /// 边列边返回目录下的文件和目录（不含目录自身），不会把整棵树放进内存
+    /// - 深度优先，每次只列一个目录（`Depth: 1`），目录里的项边收边返回，消费方不拉取时不会发请求
+    /// - 某个目录列出失败时返回一条 `Err`，之后继续遍历其他目录，由调用方决定是否停止
+    /// - 返回的 [`FriendlyResource::full_path`] 已解析为完整 URL
+    fn walk_stream<'a>(
+        &'a self,
+        web_dav_child_client_key: &WebDavChildClientKey,
+        path: &str,
+        walk_config: WalkConfig,
+    ) -> TResourceStream<'a>;
+}
@@ -1,7 +1,120 @@
+use crate::client::error::WebDavClientError;
+use crate::client::structs::progress::TProgressSender;
+use crate::client::structs::transfer_control::TransferControl;
+use crate::client::structs::webdav_child_client::WebDavChildClientKey;
+use crate::client::traits::download::{
+    DEFAULT_MAX_CONCURRENCY, ThreadMode,
+};
+use async_trait::async_trait;
+
+pub struct UploadConfig {
+    /// 线程模式
+    pub thread_mode: ThreadMode,
+    /// 远端已存在同名文件时是否覆盖
+    /// - 为 `false` 时会带上 `If-None-Match: *`，已存在的文件会被服务端拒绝
+    pub overwrite: bool,
+    /// 本次调用同时上传的文件数，只在多线程模式下生效
+    /// - 另外还受 `WebDavClient` 传输池的全局/主机/账号上限约束
+    pub max_concurrency: usize,
+    /// 进度事件的发送端，为 `None` 时不汇报进度
+    pub progress: Option<TProgressSender>,
+    /// 暂停/继续/取消的控制句柄，为 `None` 时无法中途停止
+    pub control: Option<TransferControl>,
+    /// 按服务商配置把本地修改时间带到远端（`X-OC-Mtime` 或 PROPPATCH）
+    /// - 服务商配置里没有相应方式时不做处理
+    /// - 写入失败不影响上传结果，看 [`UploadFileReport::mtime_preserved`]
+    pub preserve_mtime: bool,
+}
+
+impl UploadConfig {
+    pub fn new(thread_mode: ThreadMode, overwrite: bool) -> Self {
+        Self {
+            thread_mode,
+            overwrite,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            progress: None,
+            control: None,
+            preserve_mtime: true,
+        }
+    }
+
+    pub fn new_default_config() -> Self {
+        Self::new(ThreadMode::Auto, true)
+    }
+
+    /// 设置本次调用同时上传的文件数
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// 设置进度事件的发送端
+    pub fn with_progress(mut self, progress: TProgressSender) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 设置控制句柄，调用方保留它的 clone 来暂停/继续/取消本次上传
+    pub fn with_control(mut self, control: TransferControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// 设置是否把本地修改时间带到远端
+    pub fn with_preserve_mtime(mut self, preserve_mtime: bool) -> Self {
+        self.preserve_mtime = preserve_mtime;
+        self
+    }
+}
+
+/// 单个上传条目（文件或目录）的结果
+#[derive(Debug)]
+pub struct UploadFileReport {
+    /// 本地路径
+    pub local_path: String,
+    /// 远端完整 URL
+    pub remote_url: String,
+    /// 是否目录（目录对应的是 MKCOL）
+    pub is_dir: bool,
+    /// 已发送的字节数，目录恒为 0
+    pub bytes: u64,
+    /// 上传结果
+    pub result: Result<(), WebDavClientError>,
+    /// 服务端已确认保留本地修改时间，目录和失败的条目恒为 `false`
+    pub mtime_preserved: bool,
+}
+
+/// 一次 `upload_files` 调用的完整结果，按处理顺序排列
+#[derive(Debug, Default)]
+pub struct UploadReport {
+    pub files: Vec<UploadFileReport>,
+}
+
+impl UploadReport {
+    /// 成功的条目数
+    pub fn success_count(&self) -> usize {
+        self.files.iter().filter(|f| f.result.is_ok()).count()
+    }
+
+    /// 失败的条目，方便调用方只重试这一部分
+    pub fn failed(&self) -> Vec<&UploadFileReport> {
+        self.files.iter().filter(|f| f.result.is_err()).collect()
+    }
+}
+
+#[async_trait]
 pub trait Upload {
-    fn put_file(
+    /// 把本地文件或目录上传到 `remote_dir` 下
+    ///
+    /// - 文件体直接从磁盘流式读取，不会整块读进内存
+    /// - `remote_dir` 及本地目录对应的远端目录不存在时会自动 MKCOL
+    /// - 本地目录会递归上传，远端保留同样的目录结构
+    /// - 单个文件失败不会中断整体，结果记录在 [`UploadReport`] 里
+    async fn upload_files(
         &self,
-        file_path: &str,
-        config: Option<String>,
-    ) -> impl Future<Output = String> + Send;
+        web_dav_child_client_key: &WebDavChildClientKey,
+        local_paths: Vec<String>,
+        remote_dir: &str,
+        upload_config: Option<UploadConfig>,
+    ) -> Result<UploadReport, WebDavClientError>;
 }
@@ -1,14 +1,31 @@
 use crate::client::error::WebDavClientError;
 use reqwest::Method;
 
+#[allow(clippy::upper_case_acronyms)]
 pub enum WebDavMethod {
     PROPFIND,
+    PROPPATCH,
+    MKCOL,
+    DELETE,
+    MOVE,
+    COPY,
+    SEARCH,
+    LOCK,
+    UNLOCK,
 }
 
 impl WebDavMethod {
     pub fn to_string(&self) -> String {
         match self {
             WebDavMethod::PROPFIND => "PROPFIND".to_string(),
+            WebDavMethod::PROPPATCH => "PROPPATCH".to_string(),
+            WebDavMethod::MKCOL => "MKCOL".to_string(),
+            WebDavMethod::DELETE => "DELETE".to_string(),
+            WebDavMethod::MOVE => "MOVE".to_string(),
+            WebDavMethod::COPY => "COPY".to_string(),
+            WebDavMethod::SEARCH => "SEARCH".to_string(),
+            WebDavMethod::LOCK => "LOCK".to_string(),
+            WebDavMethod::UNLOCK => "UNLOCK".to_string(),
         }
     }
 }
@@ -22,7 +39,15 @@ impl TryInto<Method> for WebDavMethod {
                 .map_err(|e| WebDavClientError::String(e.to_string()))?;
 
         match self {
-            WebDavMethod::PROPFIND => Ok(method),
+            WebDavMethod::PROPFIND
+            | WebDavMethod::PROPPATCH
+            | WebDavMethod::MKCOL
+            | WebDavMethod::DELETE
+            | WebDavMethod::MOVE
+            | WebDavMethod::COPY
+            | WebDavMethod::SEARCH
+            | WebDavMethod::LOCK
+            | WebDavMethod::UNLOCK => Ok(method),
         }
     }
 }
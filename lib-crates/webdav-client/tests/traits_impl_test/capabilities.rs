@@ -0,0 +1,74 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::capabilities::Capabilities;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+#[tokio::test]
+async fn test_get_capabilities() -> Result<(), WebDavClientError> {
+    println!("======服务端能力探测测试开始======");
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let test_data =
+        vec![(WEBDAV_ENV_PATH_1, true), (WEBDAV_ENV_PATH_2, true)];
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = client.get_capabilities(&key).await;
+
+        // 缓存和重新探测的结果应该一致，且都是 WebDAV 1 级
+        let is_ok = match &result {
+            Ok(capabilities) => {
+                let refreshed = client.refresh_capabilities(&key).await?;
+                capabilities.supports_class("1")
+                    && refreshed.dav_classes == capabilities.dav_classes
+            }
+            Err(_) => false,
+        };
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(capabilities) => {
+                    println!("✅ {env_path}: {capabilities:?}")
+                }
+                Err(e) => println!("❌ {env_path}: {e}"),
+            }
+        }
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "服务端能力探测失败: {}",
+            env_path
+        );
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "服务端能力探测",
+    );
+
+    println!("======服务端能力探测测试结束======");
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::assert_test_result;
+use webdav_client::client::structs::digest_auth::DigestChallenge;
+
+#[test]
+fn test_parse_digest_challenge() {
+    println!("======Digest 质询解析测试开始======");
+
+    // (WWW-Authenticate, 期望解析出 realm="r"、nonce="n" 的 Digest 质询)
+    let test_data = vec![
+        (r#"Digest realm="r", nonce="n", qop="auth""#, true),
+        (r#"digest realm="r", nonce="n""#, true),
+        // 前一个方案的引号里出现 digest
+        (
+            r#"Basic realm="use digest auth", Digest realm="r", nonce="n""#,
+            true,
+        ),
+        (r#"Digest realm="r", nonce="n", Basic realm="b""#, true),
+        (
+            r#"Negotiate, Digest realm="r", nonce="n", algorithm=SHA-256"#,
+            true,
+        ),
+        (r#"Basic realm="digest nonce=x""#, false),
+        (r#"Bearer realm="Digest realm=r, nonce=n""#, false),
+        (r#"Digest realm="r""#, false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (header, expected_ok) in &test_data {
+        let authorization = DigestChallenge::parse(header).map(|mut c| {
+            c.authorization("user", "password", "PROPFIND", "/dav/")
+        });
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{header} -> {authorization:?}");
+        }
+
+        let is_ok = authorization.is_some_and(|authorization| {
+            authorization.contains(r#"realm="r""#)
+                && authorization.contains(r#"nonce="n""#)
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "Digest 质询解析测试失败: {header}"
+        );
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "Digest 质询解析",
+    );
+
+    println!("======Digest 质询解析测试结束======");
+}
@@ -0,0 +1,808 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use std::path::Path;
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::progress::{
+    TransferEvent, progress_channel,
+};
+use webdav_client::client::structs::transfer_control::TransferControl;
+use webdav_client::client::structs::transfer_pool::TransferLimits;
+use webdav_client::client::traits::download::{
+    Download, DownloadConfig, DownloadOutcome, ThreadMode,
+};
+use webdav_client::client::traits::folder::Folder;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+use webdav_client::client::traits::upload::Upload;
+use webdav_client::public_traits::friendly::FriendlyXml;
+
+/// 先上传一个小文件，保证远端有东西可下
+async fn prepare_remote_file(
+    client: &mut WebDavClient,
+    env_path: &str,
+) -> Result<String, WebDavClientError> {
+    let local_dir = std::env::temp_dir().join("quick-sync-download-src");
+    std::fs::create_dir_all(&local_dir)?;
+    let local_file = local_dir.join("下载测试.txt");
+    std::fs::write(
+        &local_file,
+        format!("quick-sync download {env_path}"),
+    )?;
+
+    let acc = load_account(env_path);
+    let key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+    let report = client
+        .upload_files(
+            &key,
+            vec![local_file.to_string_lossy().to_string()],
+            "./下载测试",
+            None,
+        )
+        .await?;
+    assert!(report.failed().is_empty(), "准备下载测试文件失败");
+
+    Ok("./下载测试/下载测试.txt".to_string())
+}
+
+#[tokio::test]
+async fn test_download_files() -> Result<(), WebDavClientError> {
+    println!("======下载文件测试开始======");
+
+    let output_dir = std::env::temp_dir().join("quick-sync-download-test");
+    std::fs::create_dir_all(&output_dir)?;
+    let output_dir = output_dir.to_string_lossy().to_string();
+
+    let mut client = WebDavClient::new();
+
+    let remote_file_1 =
+        prepare_remote_file(&mut client, WEBDAV_ENV_PATH_1).await?;
+    let remote_file_2 =
+        prepare_remote_file(&mut client, WEBDAV_ENV_PATH_2).await?;
+
+    // (账号, 远端路径, 是否应当成功)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, remote_file_1.clone(), true),
+        (WEBDAV_ENV_PATH_2, remote_file_2.clone(), true),
+        (
+            WEBDAV_ENV_PATH_1,
+            "./不存在的文件-quick-sync.txt".to_string(),
+            false,
+        ),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, remote_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let report = client
+            .download_files(
+                &key,
+                vec![remote_path.clone()],
+                &output_dir,
+                None,
+            )
+            .await?;
+
+        assert_eq!(report.files.len(), 1, "每个请求路径都应该有一条报告");
+        let file = &report.files[0];
+        assert_eq!(&file.path, remote_path);
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{} {} -> {:?} ({}B, {:?})",
+                if file.is_ok() { "✅" } else { "❌" },
+                file.path,
+                file.local_path,
+                file.bytes,
+                file.elapsed
+            );
+        }
+
+        let is_ok = matches!(file.outcome, DownloadOutcome::Downloaded);
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "下载测试失败: {} -> {}",
+            env_path, remote_path
+        );
+    }
+
+    // 再下载一次，本地已有完整文件时应该跳过
+    let acc = load_account(WEBDAV_ENV_PATH_1);
+    let key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+    let report = client
+        .download_files(
+            &key,
+            vec![remote_file_1],
+            &output_dir,
+            Some(
+                DownloadConfig::new_default_config()
+                    .with_skip_existing(true),
+            ),
+        )
+        .await?;
+    assert!(
+        matches!(report.files[0].outcome, DownloadOutcome::Skipped),
+        "本地已有完整文件时应该跳过"
+    );
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "下载文件",
+    );
+
+    println!("======下载文件测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_segmented_integrity()
+-> Result<(), WebDavClientError> {
+    println!("======分片下载校验测试开始======");
+
+    let output_dir =
+        std::env::temp_dir().join("quick-sync-download-segmented-test");
+    let _ = std::fs::remove_dir_all(&output_dir);
+    std::fs::create_dir_all(&output_dir)?;
+    let output_dir_str = output_dir.to_string_lossy().to_string();
+
+    let mut client = WebDavClient::new();
+    let remote_file =
+        prepare_remote_file(&mut client, WEBDAV_ENV_PATH_1).await?;
+
+    let acc = load_account(WEBDAV_ENV_PATH_1);
+    let key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+    // 很小的分片，让每个分片的 Content-Range 和拼接都被校验到
+    let report = client
+        .download_files(
+            &key,
+            vec![remote_file],
+            &output_dir_str,
+            Some(
+                DownloadConfig::new_default_config()
+                    .with_segments(4, 3)
+                    .with_verify_checksum(true),
+            ),
+        )
+        .await?;
+
+    let file = &report.files[0];
+
+    #[cfg(feature = "show-test-detail")]
+    {
+        println!("{:?}", file);
+    }
+
+    assert!(file.is_ok(), "分片下载失败: {:?}", file.outcome);
+
+    let content =
+        std::fs::read_to_string(output_dir.join("下载测试.txt"))?;
+    assert_eq!(
+        content,
+        format!("quick-sync download {WEBDAV_ENV_PATH_1}"),
+        "分片下载的内容和上传的不一致"
+    );
+
+    println!("======分片下载校验测试结束======");
+    Ok(())
+}
+
+/// 续传测试用的大文件，保证取消时还没下载完
+const RESUME_FILE_SIZE: usize = 8 * 1024 * 1024;
+
+/// 上传一个内容可预测的大文件，返回远端路径和原始内容
+async fn prepare_large_remote_file(
+    client: &mut WebDavClient,
+    env_path: &str,
+) -> Result<(String, Vec<u8>), WebDavClientError> {
+    let local_dir = std::env::temp_dir().join("quick-sync-download-src");
+    std::fs::create_dir_all(&local_dir)?;
+    let local_file = local_dir.join("续传测试.bin");
+    let content: Vec<u8> =
+        (0..RESUME_FILE_SIZE).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&local_file, &content)?;
+
+    let acc = load_account(env_path);
+    let key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+    let report = client
+        .upload_files(
+            &key,
+            vec![local_file.to_string_lossy().to_string()],
+            "./下载测试",
+            None,
+        )
+        .await?;
+    assert!(report.failed().is_empty(), "准备续传测试文件失败");
+
+    Ok(("./下载测试/续传测试.bin".to_string(), content))
+}
+
+/// 下载结果和远端一致，并且没有留下 `.part`/`.part.json`
+fn check_finished_file(
+    output_dir: &Path,
+    content: &[u8],
+) -> Result<bool, WebDavClientError> {
+    let output_file = output_dir.join("续传测试.bin");
+    let downloaded = std::fs::read(&output_file)?;
+    let part = output_dir.join("续传测试.bin.part");
+    let record = output_dir.join("续传测试.bin.part.json");
+
+    Ok(downloaded == content && !part.exists() && !record.exists())
+}
+
+#[tokio::test]
+async fn test_download_resume() -> Result<(), WebDavClientError> {
+    println!("======断点续传测试开始======");
+
+    let output_dir =
+        std::env::temp_dir().join("quick-sync-download-resume-test");
+
+    let mut client = WebDavClient::new();
+
+    // (账号, 是否分片)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, false),
+        (WEBDAV_ENV_PATH_1, true),
+        (WEBDAV_ENV_PATH_2, false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, segmented) in &test_data {
+        let (remote_file, content) =
+            prepare_large_remote_file(&mut client, env_path).await?;
+
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let _ = std::fs::remove_dir_all(&output_dir);
+        std::fs::create_dir_all(&output_dir)?;
+        let output_dir_str = output_dir.to_string_lossy().to_string();
+
+        // 收到第一条字节进度就取消，保留 `.part`
+        let control = TransferControl::new().keep_partial_on_cancel(true);
+        let (sender, mut receiver) = progress_channel();
+        let canceller = control.clone();
+        let watcher = tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if matches!(event, TransferEvent::BytesTransferred { .. })
+                {
+                    canceller.cancel();
+                    break;
+                }
+            }
+        });
+
+        let report = client
+            .download_files(
+                &key,
+                vec![remote_file.clone()],
+                &output_dir_str,
+                Some(
+                    DownloadConfig::new(
+                        ThreadMode::SingleThread,
+                        *segmented,
+                    )
+                    .with_progress(sender)
+                    .with_control(control),
+                ),
+            )
+            .await?;
+        watcher.abort();
+
+        let interrupted =
+            matches!(
+                report.files[0].outcome,
+                DownloadOutcome::Failed(WebDavClientError::Cancelled)
+            ) && output_dir.join("续传测试.bin.part").exists();
+
+        // 重新下载，应该从 `.part` 接着写
+        let report = client
+            .download_files(
+                &key,
+                vec![remote_file],
+                &output_dir_str,
+                Some(DownloadConfig::new(
+                    ThreadMode::SingleThread,
+                    *segmented,
+                )),
+            )
+            .await?;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path} 分片={segmented}: 中断={interrupted}, {:?}",
+                report.files[0]
+            );
+        }
+
+        let is_ok = interrupted
+            && report.files[0].is_ok()
+            && check_finished_file(&output_dir, &content)?;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(is_ok, "断点续传测试失败: {env_path} 分片={segmented}");
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "断点续传",
+    );
+
+    println!("======断点续传测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_resume_complete_record()
+-> Result<(), WebDavClientError> {
+    println!("======续传记录已完整测试开始======");
+
+    let output_dir =
+        std::env::temp_dir().join("quick-sync-download-complete-test");
+
+    let mut client = WebDavClient::new();
+
+    // (账号, 是否分片)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, false),
+        (WEBDAV_ENV_PATH_1, true),
+        (WEBDAV_ENV_PATH_2, false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, segmented) in &test_data {
+        let (remote_file, content) =
+            prepare_large_remote_file(&mut client, env_path).await?;
+
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let _ = std::fs::remove_dir_all(&output_dir);
+        std::fs::create_dir_all(&output_dir)?;
+        let output_dir_str = output_dir.to_string_lossy().to_string();
+
+        // 模拟最后一次提交之后、改名之前进程退出：`.part` 已完整，记录也是完整大小
+        let resource = client
+            .get_file_meta(&key, &remote_file)
+            .await?
+            .to_friendly()?
+            .into_iter()
+            .next()
+            .expect("服务端没有返回资源信息");
+        std::fs::write(output_dir.join("续传测试.bin.part"), &content)?;
+        let record = serde_json::json!({
+            "url": resource.full_path,
+            "etag": resource.etag,
+            "last_modified": resource.last_modified.map(|t| t.to_rfc2822()),
+            "size": content.len(),
+            "downloaded": content.len(),
+        });
+        std::fs::write(
+            output_dir.join("续传测试.bin.part.json"),
+            record.to_string(),
+        )?;
+
+        let report = client
+            .download_files(
+                &key,
+                vec![remote_file],
+                &output_dir_str,
+                Some(DownloadConfig::new(
+                    ThreadMode::SingleThread,
+                    *segmented,
+                )),
+            )
+            .await?;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{env_path} 分片={segmented}: {:?}", report.files[0]);
+        }
+
+        let is_ok = report.files[0].is_ok()
+            && check_finished_file(&output_dir, &content)?;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(
+            is_ok,
+            "续传记录已完整测试失败: {env_path} 分片={segmented}"
+        );
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "续传记录已完整",
+    );
+
+    println!("======续传记录已完整测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_streaming() -> Result<(), WebDavClientError> {
+    println!("======流式下载测试开始======");
+
+    let output_dir =
+        std::env::temp_dir().join("quick-sync-download-streaming-test");
+
+    let mut client = WebDavClient::new();
+
+    // (账号, 是否分片)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, false),
+        (WEBDAV_ENV_PATH_1, true),
+        (WEBDAV_ENV_PATH_2, false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, segmented) in &test_data {
+        let (remote_file, content) =
+            prepare_large_remote_file(&mut client, env_path).await?;
+
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let _ = std::fs::remove_dir_all(&output_dir);
+        std::fs::create_dir_all(&output_dir)?;
+        let output_dir_str = output_dir.to_string_lossy().to_string();
+
+        let (sender, mut receiver) = progress_channel();
+        let report = client
+            .download_files(
+                &key,
+                vec![remote_file],
+                &output_dir_str,
+                Some(
+                    DownloadConfig::new(
+                        ThreadMode::SingleThread,
+                        *segmented,
+                    )
+                    .with_progress(sender),
+                ),
+            )
+            .await?;
+
+        // 边收边写时，完成前会陆续收到多条字节进度
+        let mut progress_events = 0;
+        while let Ok(event) = receiver.try_recv() {
+            if matches!(event, TransferEvent::BytesTransferred { .. }) {
+                progress_events += 1;
+            }
+        }
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path} 分片={segmented}: {progress_events} 条进度, {:?}",
+                report.files[0]
+            );
+        }
+
+        let is_ok = report.files[0].is_ok()
+            && report.files[0].bytes == content.len() as u64
+            && progress_events > 1
+            && check_finished_file(&output_dir, &content)?;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(is_ok, "流式下载测试失败: {env_path} 分片={segmented}");
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "流式下载",
+    );
+
+    println!("======流式下载测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_parallel_segments() -> Result<(), WebDavClientError>
+{
+    println!("======并发分片下载测试开始======");
+
+    let whole_dir =
+        std::env::temp_dir().join("quick-sync-download-whole-test");
+    let parallel_dir =
+        std::env::temp_dir().join("quick-sync-download-parallel-test");
+
+    let mut client = WebDavClient::new();
+
+    // (账号, 分片大小, 单文件并发分片数)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, 1024 * 1024, 4),
+        // 分片大小不整除文件大小，最后一片较短
+        (WEBDAV_ENV_PATH_1, 1000 * 1000, 3),
+        (WEBDAV_ENV_PATH_2, 1024 * 1024, 4),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, segment_size, concurrency) in &test_data {
+        let (remote_file, content) =
+            prepare_large_remote_file(&mut client, env_path).await?;
+
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        for dir in [&whole_dir, &parallel_dir] {
+            let _ = std::fs::remove_dir_all(dir);
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let whole = client
+            .download_files(
+                &key,
+                vec![remote_file.clone()],
+                &whole_dir.to_string_lossy(),
+                Some(DownloadConfig::new(ThreadMode::SingleThread, false)),
+            )
+            .await?;
+
+        let parallel = client
+            .download_files(
+                &key,
+                vec![remote_file],
+                &parallel_dir.to_string_lossy(),
+                Some(
+                    DownloadConfig::new(ThreadMode::MultipleThread, true)
+                        .with_segments(*segment_size, *concurrency),
+                ),
+            )
+            .await?;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{env_path} 整文件: {:?}", whole.files[0]);
+            println!(
+                "{env_path} 分片 {segment_size}x{concurrency}: {:?}",
+                parallel.files[0]
+            );
+        }
+
+        let is_ok = whole.files[0].is_ok()
+            && parallel.files[0].is_ok()
+            && std::fs::read(whole_dir.join("续传测试.bin"))?
+                == std::fs::read(parallel_dir.join("续传测试.bin"))?
+            && check_finished_file(&parallel_dir, &content)?;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(
+            is_ok,
+            "并发分片下载测试失败: {env_path} {segment_size}x{concurrency}"
+        );
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "并发分片下载",
+    );
+
+    println!("======并发分片下载测试结束======");
+    Ok(())
+}
+
+/// 上传一个带子目录的小目录，返回远端路径
+async fn prepare_remote_dir(
+    client: &mut WebDavClient,
+    env_path: &str,
+) -> Result<String, WebDavClientError> {
+    let local_dir = std::env::temp_dir()
+        .join("quick-sync-download-src")
+        .join("传输池测试");
+    std::fs::create_dir_all(local_dir.join("子目录"))?;
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(local_dir.join(name), format!("pool {name}"))?;
+    }
+    std::fs::write(local_dir.join("子目录").join("d.txt"), "pool d.txt")?;
+
+    let acc = load_account(env_path);
+    let key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+    let report = client
+        .upload_files(
+            &key,
+            vec![local_dir.to_string_lossy().to_string()],
+            "./下载测试",
+            None,
+        )
+        .await?;
+    assert!(report.failed().is_empty(), "准备传输池测试目录失败");
+
+    Ok("./下载测试/传输池测试".to_string())
+}
+
+#[tokio::test]
+async fn test_download_tree_through_pool() -> Result<(), WebDavClientError>
+{
+    println!("======目录经传输池下载测试开始======");
+
+    let output_dir =
+        std::env::temp_dir().join("quick-sync-download-pool-test");
+
+    // 三级上限都是 1：目录本身不能占许可，否则会等自己的文件
+    let mut client =
+        WebDavClient::with_transfer_limits(TransferLimits::new(1, 1, 1));
+
+    // (账号, 线程模式, 占住的本地文件, 期望成功)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, ThreadMode::MultipleThread, None, true),
+        (WEBDAV_ENV_PATH_1, ThreadMode::SingleThread, None, true),
+        // 一个文件失败时其余文件照常下载，目录记为失败
+        (
+            WEBDAV_ENV_PATH_1,
+            ThreadMode::MultipleThread,
+            Some("b.txt"),
+            false,
+        ),
+        (WEBDAV_ENV_PATH_2, ThreadMode::MultipleThread, None, true),
+    ];
+
+    let expected_ok_count =
+        test_data.iter().filter(|(.., ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, thread_mode, blocked, expected_ok) in test_data {
+        let remote_dir = prepare_remote_dir(&mut client, env_path).await?;
+
+        let acc = load_account(env_path);
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let _ = std::fs::remove_dir_all(&output_dir);
+        let local_dir = output_dir.join("传输池测试");
+        std::fs::create_dir_all(&local_dir)?;
+
+        // 在文件的位置放一个非空目录，改名时必然失败
+        if let Some(blocked) = blocked {
+            std::fs::create_dir_all(local_dir.join(blocked).join("占位"))?;
+        }
+
+        let (sender, mut receiver) = progress_channel();
+        let report = tokio::time::timeout(
+            std::time::Duration::from_secs(120),
+            client.download_files(
+                &key,
+                vec![remote_dir],
+                &output_dir.to_string_lossy(),
+                Some(
+                    DownloadConfig::new(thread_mode, false)
+                        .with_max_concurrency(4)
+                        .with_progress(sender),
+                ),
+            ),
+        )
+        .await
+        .expect("上限为 1 时目录下载不应卡住")?;
+
+        // 上限为 1 时文件只能一个接一个地传
+        let mut running = 0;
+        let mut peak = 0;
+        let mut finished = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                TransferEvent::FileStarted { .. } => {
+                    running += 1;
+                    peak = peak.max(running);
+                }
+                TransferEvent::FileFinished { local_path, .. } => {
+                    running -= 1;
+                    finished.push(local_path);
+                }
+                TransferEvent::FileFailed { .. } => running -= 1,
+                _ => {}
+            }
+        }
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path}: 同时最多 {peak} 个, 完成 {finished:?}, {:?}",
+                report.files[0]
+            );
+        }
+
+        let all_others_done = ["a.txt", "b.txt", "c.txt", "子目录/d.txt"]
+            .iter()
+            .filter(|name| Some(**name) != blocked)
+            .all(|name| local_dir.join(name).is_file());
+
+        assert_eq!(peak, 1, "传输池上限为 1 时文件不应同时传输");
+        assert!(all_others_done, "其余文件应该都下载完成: {env_path}");
+
+        let is_ok = report.files[0].is_ok();
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, expected_ok,
+            "目录经传输池下载测试失败: {env_path} {blocked:?}"
+        );
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "目录经传输池下载",
+    );
+
+    println!("======目录经传输池下载测试结束======");
+    Ok(())
+}
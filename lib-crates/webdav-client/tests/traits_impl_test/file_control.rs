@@ -0,0 +1,123 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::webdav_child_client::WebDavChildClientKey;
+use webdav_client::client::traits::file_control::FileControl;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+/// 在测试目录里走一遍 mkdir -> copy -> rename -> move -> rm_file -> rmdir
+async fn run_file_control(
+    client: &WebDavClient,
+    key: &WebDavChildClientKey,
+) -> Result<(), WebDavClientError> {
+    client.mkdir(key, "./", "文件操作测试").await?;
+    client.mkdir(key, "./文件操作测试", "子目录").await?;
+
+    client
+        .copy_item(
+            key,
+            "./算法与分析.nol",
+            "./文件操作测试/副本.nol",
+            true,
+        )
+        .await?;
+    client.rename(key, "./文件操作测试/副本.nol", "改名 #1.nol").await?;
+    client
+        .move_item(
+            key,
+            "./文件操作测试/改名%20%231.nol",
+            "./文件操作测试/子目录/改名.nol",
+            false,
+        )
+        .await?;
+    client.rm_file(key, "./文件操作测试/子目录/改名.nol", false).await?;
+
+    client.rmdir(key, "./文件操作测试", true).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_control() -> Result<(), WebDavClientError> {
+    println!("======文件操作测试开始======");
+
+    let test_data = vec![(WEBDAV_ENV_PATH_1, true)];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = run_file_control(&client, &key).await;
+        let is_ok = result.is_ok();
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            #[cfg(feature = "show-test-detail")]
+            {
+                println!(
+                    "❌ 账号: {env_path} 错误: {}",
+                    result.unwrap_err()
+                );
+            }
+            err_count += 1;
+        }
+
+        assert_eq!(is_ok, *expected_ok, "文件操作测试失败: {}", env_path);
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "文件操作",
+    );
+
+    println!("======文件操作测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_control_out_of_base() -> Result<(), WebDavClientError> {
+    println!("======越界操作测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_2, "./test.txt", "../test.txt"),
+        (WEBDAV_ENV_PATH_2, "./test.txt", "/test.txt"),
+        (WEBDAV_ENV_PATH_2, "./", "./根目录副本"),
+    ];
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, from_path, to_path) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result =
+            client.move_item(&key, from_path, to_path, false).await;
+
+        assert!(
+            result.is_err(),
+            "越界移动未被拦截: {} -> {}",
+            from_path,
+            to_path
+        );
+    }
+
+    println!("======越界操作测试结束======");
+    Ok(())
+}
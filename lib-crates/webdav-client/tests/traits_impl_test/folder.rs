@@ -1,9 +1,13 @@
 use crate::{
     WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
 };
+use futures_util::TryStreamExt;
 use webdav_client::client::WebDavClient;
 use webdav_client::client::enums::client_enum::Depth;
 use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::propfind::{
+    PropName, PropfindRequest,
+};
 #[cfg(feature = "show-test-detail")]
 use webdav_client::client::structs::raw_xml::MultiStatus;
 use webdav_client::client::traits::folder::Folder;
@@ -162,3 +166,176 @@ async fn test_get_folders() -> Result<(), WebDavClientError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_propfind_selected_props() -> Result<(), WebDavClientError> {
+    println!("======指定属性PROPFIND测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./算法与分析.nol", true),
+        (WEBDAV_ENV_PATH_1, "./不存在的文件.txt", false),
+        (WEBDAV_ENV_PATH_2, "./test.txt", true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    // 服务端没有的自定义属性应该出现在 404 的 propstat 里
+    let missing = PropName::new("urn:webdav-client:test", "missing");
+    let propfind_request = PropfindRequest::prop()
+        .with_dav_prop("resourcetype")
+        .with_dav_prop("getetag")
+        .with_prop_name(missing.clone());
+
+    for (env_path, file_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = client
+            .propfind(&key, file_path, &Depth::Zero, &propfind_request)
+            .await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(multi_status) => {
+                    println!(
+                        "✅ {env_path} -> {file_path}: {multi_status:?}"
+                    )
+                }
+                Err(e) => println!("❌ {env_path} -> {file_path}: {e}"),
+            }
+        }
+
+        let is_ok = result.is_ok_and(|multi_status| {
+            let propstats = multi_status
+                .responses
+                .iter()
+                .flat_map(|response| &response.propstats);
+
+            propstats.clone().any(|p| p.prop.etag.is_some())
+                && propstats
+                    .clone()
+                    .any(|p| p.prop.dead_props.contains_key(&missing))
+                // 没有请求的属性不应该返回
+                && propstats.clone().all(|p| p.prop.display_name.is_none())
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "指定属性PROPFIND测试失败: {} -> {}",
+            env_path, file_path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "指定属性PROPFIND",
+    );
+
+    println!("======指定属性PROPFIND测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_folders_stream() -> Result<(), WebDavClientError> {
+    println!("======流式读取文件夹测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./", true),
+        (WEBDAV_ENV_PATH_2, "./", true),
+        (WEBDAV_ENV_PATH_1, "./不存在的目录", false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, folder_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = match client
+            .get_folders_stream(&key, folder_path, &Depth::One)
+            .await
+        {
+            Ok(stream) => stream.try_collect::<Vec<_>>().await,
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(responses) => println!(
+                    "✅ {env_path} -> {folder_path}: {} 项",
+                    responses.len()
+                ),
+                Err(e) => println!("❌ {env_path} -> {folder_path}: {e}"),
+            }
+        }
+
+        // 和一次性读取的结果一致
+        let is_ok = match result {
+            Ok(responses) => {
+                let multi_status = client
+                    .get_folders(&key, folder_path, &Depth::One)
+                    .await?;
+                let hrefs: Vec<_> =
+                    responses.iter().map(|r| &r.href).collect();
+                let expected: Vec<_> = multi_status
+                    .responses
+                    .iter()
+                    .map(|r| &r.href)
+                    .collect();
+                !hrefs.is_empty() && hrefs == expected
+            }
+            Err(_) => false,
+        };
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "流式读取文件夹测试失败: {} -> {}",
+            env_path, folder_path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "流式读取文件夹",
+    );
+
+    println!("======流式读取文件夹测试结束======");
+    Ok(())
+}
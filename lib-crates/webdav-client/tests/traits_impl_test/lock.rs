@@ -0,0 +1,109 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use std::time::Duration;
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::lock::LockOptions;
+use webdav_client::client::traits::lock::Lock;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+#[tokio::test]
+async fn test_lock_unlock() -> Result<(), WebDavClientError> {
+    println!("======加锁解锁测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./算法与分析.nol", true),
+        (WEBDAV_ENV_PATH_2, "./test.txt", true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, file_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let mut client = WebDavClient::new();
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        // 另一台机器上的客户端
+        let mut other = WebDavClient::new();
+        let other_key =
+            other.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let lock_options = LockOptions::new_default_config()
+            .with_timeout(Some(Duration::from_secs(120)))
+            .with_owner("webdav-client test");
+
+        let lock = client.lock(&key, file_path, Some(lock_options)).await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{env_path} -> {file_path} 加锁结果: {lock:?}");
+        }
+
+        let is_ok = match lock {
+            Ok(lock) => {
+                let discovered =
+                    client.discover_locks(&key, file_path).await;
+                let held = client.held_locks(&key).await?;
+                let refreshed = client
+                    .refresh_lock(
+                        &key,
+                        file_path,
+                        Some(Duration::from_secs(60)),
+                    )
+                    .await;
+                // 独占锁还没释放，别人加锁应该失败
+                let conflict =
+                    other.lock(&other_key, file_path, None).await;
+                let unlocked = client.unlock(&key, file_path).await;
+
+                #[cfg(feature = "show-test-detail")]
+                {
+                    println!("发现的锁: {discovered:?}");
+                    println!("刷新结果: {refreshed:?}");
+                    println!("冲突加锁: {conflict:?}");
+                    println!("解锁结果: {unlocked:?}");
+                }
+
+                discovered.is_ok_and(|locks| {
+                    locks.iter().any(|l| l.token == lock.token)
+                }) && held.iter().any(|l| l.token == lock.token)
+                    && refreshed.is_ok()
+                    && conflict.is_err()
+                    && unlocked.is_ok()
+                    && client.held_locks(&key).await?.is_empty()
+            }
+            Err(_) => false,
+        };
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "加锁解锁测试失败: {} -> {}",
+            env_path, file_path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "加锁解锁",
+    );
+
+    println!("======加锁解锁测试结束======");
+    Ok(())
+}
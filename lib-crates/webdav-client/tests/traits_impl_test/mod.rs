@@ -1,3 +1,18 @@
 mod url_trait;
 mod folder;
-mod safe_atomic_ops;
\ No newline at end of file
+mod safe_atomic_ops;
+mod upload;
+mod file_control;
+mod search;
+mod download;
+mod capabilities;
+mod provider_profile;
+mod quota;
+mod lock;
+mod properties;
+mod mtime;
+mod retry_policy;
+mod tree_walk;
+mod multistatus_parse;
+mod transfer_pool;
+mod digest_auth;
\ No newline at end of file
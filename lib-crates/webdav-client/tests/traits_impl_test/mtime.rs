@@ -0,0 +1,137 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::download::{Download, DownloadConfig};
+use webdav_client::client::traits::folder::Folder;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+use webdav_client::client::traits::upload::{Upload, UploadConfig};
+use webdav_client::public_traits::friendly::FriendlyXml;
+
+/// 2020-01-01 00:00:00 UTC
+const OLD_MTIME_SECS: u64 = 1_577_836_800;
+
+/// 准备一个修改时间较早的本地文件
+fn prepare_old_file() -> Result<std::path::PathBuf, WebDavClientError> {
+    let local_dir = std::env::temp_dir().join("quick-sync-mtime-src");
+    std::fs::create_dir_all(&local_dir)?;
+    let local_file = local_dir.join("修改时间测试.txt");
+    std::fs::write(&local_file, "quick-sync mtime test")?;
+
+    std::fs::File::options()
+        .write(true)
+        .open(&local_file)?
+        .set_modified(UNIX_EPOCH + Duration::from_secs(OLD_MTIME_SECS))?;
+    Ok(local_file)
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_preserve_mtime() -> Result<(), WebDavClientError> {
+    println!("======修改时间保留测试开始======");
+
+    let local_file = prepare_old_file()?;
+    let output_dir = std::env::temp_dir().join("quick-sync-mtime-dst");
+    std::fs::create_dir_all(&output_dir)?;
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./修改时间测试", true),
+        (WEBDAV_ENV_PATH_2, "./修改时间测试", true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (env_path, remote_dir, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let mut client = WebDavClient::new();
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let upload_report = client
+            .upload_files(
+                &key,
+                vec![local_file.to_string_lossy().to_string()],
+                remote_dir,
+                Some(UploadConfig::new_default_config()),
+            )
+            .await?;
+
+        let remote_path = format!("{remote_dir}/修改时间测试.txt");
+        let remote_mtime = client
+            .get_file_meta(&key, &remote_path)
+            .await?
+            .to_friendly()?
+            .into_iter()
+            .next()
+            .and_then(|resource| resource.last_modified)
+            .map(|time| unix_secs(SystemTime::from(time)));
+
+        let download_report = client
+            .download_files(
+                &key,
+                vec![remote_path.clone()],
+                &output_dir.to_string_lossy(),
+                Some(DownloadConfig::new_default_config()),
+            )
+            .await?;
+
+        let local_mtime =
+            std::fs::metadata(output_dir.join("修改时间测试.txt"))?
+                .modified()
+                .map(unix_secs)
+                .ok();
+
+        let mtime_preserved =
+            upload_report.files.iter().all(|f| f.mtime_preserved);
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path} 上传后远端保留修改时间: {mtime_preserved}"
+            );
+            println!("远端修改时间: {remote_mtime:?}");
+            println!("下载后本地修改时间: {local_mtime:?}");
+        }
+
+        // 下载后总是和远端一致；上传报告保留时远端也应是原来的时间
+        let is_ok = upload_report.failed().is_empty()
+            && download_report.failed().is_empty()
+            && remote_mtime.is_some()
+            && local_mtime == remote_mtime
+            && (!mtime_preserved || remote_mtime == Some(OLD_MTIME_SECS));
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "修改时间保留测试失败: {} -> {}",
+            env_path, remote_dir
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "修改时间保留",
+    );
+
+    println!("======修改时间保留测试结束======");
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use crate::assert_test_result;
+use chrono::DateTime;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::friendly_xml::FriendlyResource;
+use webdav_client::client::structs::multi_status_stream::MultiStatusParser;
+use webdav_client::client::structs::propfind::PropName;
+use webdav_client::client::structs::raw_xml::{MultiStatus, Response};
+
+/// 按固定大小分块喂给增量解析器，模拟网络分片
+fn parse_in_chunks(
+    xml: &str,
+    chunk_size: usize,
+) -> Result<Vec<Response>, WebDavClientError> {
+    let mut parser = MultiStatusParser::new();
+    let mut responses = Vec::new();
+    for chunk in xml.as_bytes().chunks(chunk_size) {
+        parser.feed(chunk);
+        while let Some(response) = parser.next_response()? {
+            responses.push(response);
+        }
+    }
+    parser.finish()?;
+    Ok(responses)
+}
+
+/// 一份录制的服务端响应及其期望
+struct RecordedCase {
+    /// 服务端
+    server: &'static str,
+    /// 响应体
+    body: &'static str,
+    /// 文件名
+    name: &'static str,
+    /// 文件大小
+    size: u64,
+    /// 期望存在的死属性
+    dead_prop: Option<PropName>,
+    /// 额外检查
+    check: fn(&FriendlyResource) -> bool,
+    /// 期望成功
+    expected_ok: bool,
+}
+
+#[test]
+fn test_parse_recorded_multistatus() {
+    println!("======录制响应解析测试开始======");
+
+    let test_data = vec![
+        RecordedCase {
+            server: "apache",
+            body: include_str!("../fixtures/multistatus/apache.xml"),
+            name: "report v2.txt",
+            size: 1337,
+            dead_prop: Some(PropName::new(
+                "http://apache.org/dav/props/",
+                "executable",
+            )),
+            check: |file| file.mime.as_deref() == Some("text/plain"),
+            expected_ok: true,
+        },
+        RecordedCase {
+            server: "nginx",
+            body: include_str!("../fixtures/multistatus/nginx.xml"),
+            name: "notes & todo.md",
+            size: 42,
+            dead_prop: None,
+            check: |file| file.etag.is_none(),
+            expected_ok: true,
+        },
+        RecordedCase {
+            server: "nextcloud",
+            body: include_str!("../fixtures/multistatus/nextcloud.xml"),
+            name: "Birdie.jpg",
+            size: 593508,
+            dead_prop: Some(PropName::new(
+                "http://nextcloud.org/ns",
+                "has-preview",
+            )),
+            check: |file| file.checksums.len() == 2,
+            expected_ok: true,
+        },
+        RecordedCase {
+            server: "jianguoyun",
+            body: include_str!("../fixtures/multistatus/jianguoyun.xml"),
+            name: "上传测试.txt",
+            size: 2048,
+            dead_prop: None,
+            check: |file| {
+                file.owner.as_deref() == Some("user@example.com")
+                    && file.privileges.len() == 5
+            },
+            expected_ok: true,
+        },
+        RecordedCase {
+            server: "teracloud",
+            body: include_str!("../fixtures/multistatus/teracloud.xml"),
+            name: "archive.zip",
+            size: 10485760,
+            dead_prop: Some(PropName::new(
+                "urn:schemas-microsoft-com:",
+                "Win32FileAttributes",
+            )),
+            check: |file| file.etag.is_some(),
+            expected_ok: true,
+        },
+        // 默认命名空间、其他命名空间里的同名属性
+        RecordedCase {
+            server: "prefixes",
+            body: include_str!("../fixtures/multistatus/prefixes.xml"),
+            name: "data.bin",
+            size: 10,
+            dead_prop: Some(PropName::new(
+                "urn:example:custom",
+                "getcontentlength",
+            )),
+            check: |file| file.etag.is_none(),
+            expected_ok: true,
+        },
+        RecordedCase {
+            server: "html",
+            body: "<html><body>502 Bad Gateway</body></html>",
+            name: "",
+            size: 0,
+            dead_prop: None,
+            check: |_| true,
+            expected_ok: false,
+        },
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for RecordedCase {
+        server,
+        body: xml,
+        name: file_name,
+        size: file_size,
+        dead_prop,
+        check,
+        expected_ok,
+    } in &test_data
+    {
+        let result = MultiStatus::from_xml(xml);
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(multi_status) => {
+                    println!("✅ {server}: {multi_status:#?}")
+                }
+                Err(e) => println!("❌ {server}: {e}"),
+            }
+        }
+
+        let is_ok = result.is_ok_and(|multi_status| {
+            // 分块增量解析和一次性解析结果一致
+            let streamed = [1, 7, 4096].iter().all(|size| {
+                parse_in_chunks(xml, *size).is_ok_and(|responses| {
+                    format!("{responses:?}")
+                        == format!("{:?}", multi_status.responses)
+                })
+            });
+
+            let has_dead_prop = dead_prop.as_ref().is_none_or(|name| {
+                multi_status.responses.iter().any(|response| {
+                    response.propstats.iter().any(|propstat| {
+                        propstat.prop.dead_props.contains_key(name)
+                    })
+                })
+            });
+
+            let resources =
+                FriendlyResource::new(multi_status).unwrap_or_default();
+            let file = resources.iter().find(|r| !r.is_dir);
+
+            streamed
+                && has_dead_prop
+                && resources.len() == 2
+                && resources[0].is_dir
+                && file.is_some_and(|file| {
+                    file.name == *file_name
+                        && file.size == Some(*file_size)
+                        && file.last_modified.is_some()
+                        && check(file)
+                })
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(is_ok, *expected_ok, "录制响应解析测试失败: {server}");
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|case| case.expected_ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "录制响应解析",
+    );
+}
+
+#[test]
+fn test_parse_lenient_dates() {
+    println!("======宽松时间解析测试开始======");
+
+    // 三个资源的时间都是 2024-05-10T00:00:00Z，最后一个格式无法识别
+    let xml = include_str!("../fixtures/multistatus/dates.xml");
+    let expected = DateTime::parse_from_rfc3339("2024-05-10T00:00:00Z")
+        .expect("合法的 RFC 3339 时间");
+
+    // (资源名, 期望解析出时间)
+    let test_data = vec![
+        ("dates", true),
+        ("asctime.txt", true),
+        ("broken.txt", false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    // 个别时间格式不对不影响整个列表
+    let resources = MultiStatus::from_xml(xml)
+        .and_then(FriendlyResource::new)
+        .expect("时间格式不对不应导致整个列表解析失败");
+
+    for (name, expected_ok) in &test_data {
+        let resource = resources.iter().find(|r| r.name == *name);
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            if let Some(resource) = resource {
+                println!(
+                    "{name}: last_modified={:?}, creation_date={:?}",
+                    resource.last_modified, resource.creation_date
+                );
+            }
+        }
+
+        let is_ok = resource.is_some_and(|resource| {
+            resource.last_modified == Some(expected)
+                && resource.creation_date == Some(expected)
+        });
+        let degraded = resource.is_some_and(|resource| {
+            resource.last_modified.is_none()
+                && resource.creation_date.is_none()
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else if degraded {
+            err_count += 1;
+        }
+
+        assert_eq!(is_ok, *expected_ok, "宽松时间解析测试失败: {name}");
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "宽松时间解析",
+    );
+}
+
+#[test]
+fn test_parse_fixtures_in_chunks() {
+    println!("======分块增量解析测试开始======");
+
+    // (录制响应, 响应体)，每份都按 1 字节和 7 字节分块喂给增量解析器
+    let test_data = vec![
+        ("apache", include_str!("../fixtures/multistatus/apache.xml")),
+        ("nginx", include_str!("../fixtures/multistatus/nginx.xml")),
+        (
+            "nextcloud",
+            include_str!("../fixtures/multistatus/nextcloud.xml"),
+        ),
+        (
+            "jianguoyun",
+            include_str!("../fixtures/multistatus/jianguoyun.xml"),
+        ),
+        (
+            "teracloud",
+            include_str!("../fixtures/multistatus/teracloud.xml"),
+        ),
+        ("prefixes", include_str!("../fixtures/multistatus/prefixes.xml")),
+        ("dates", include_str!("../fixtures/multistatus/dates.xml")),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (fixture, xml) in &test_data {
+        let expected = MultiStatus::from_xml(xml)
+            .map(|multi_status| format!("{:?}", multi_status.responses));
+
+        for chunk_size in [1, 7] {
+            let streamed = parse_in_chunks(xml, chunk_size)
+                .map(|responses| format!("{responses:?}"));
+
+            #[cfg(feature = "show-test-detail")]
+            {
+                match &streamed {
+                    Ok(_) => {
+                        println!("✅ {fixture}: {chunk_size} 字节分块")
+                    }
+                    Err(e) => {
+                        println!(
+                            "❌ {fixture}: {chunk_size} 字节分块: {e}"
+                        )
+                    }
+                }
+            }
+
+            let is_ok = matches!(
+                (&expected, &streamed),
+                (Ok(expected), Ok(streamed)) if expected == streamed
+            );
+
+            if is_ok {
+                ok_count += 1;
+            } else {
+                err_count += 1;
+            }
+
+            assert!(
+                is_ok,
+                "分块增量解析测试失败: {fixture}，{chunk_size} 字节分块"
+            );
+        }
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len() * 2,
+        0,
+        "分块增量解析",
+    );
+
+    println!("======分块增量解析测试结束======");
+}
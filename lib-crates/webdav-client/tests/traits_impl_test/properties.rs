@@ -0,0 +1,100 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::propfind::PropName;
+use webdav_client::client::structs::proppatch::PropPatchRequest;
+use webdav_client::client::traits::folder::Folder;
+use webdav_client::client::traits::properties::Properties;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+const TEST_NAMESPACE: &str = "urn:webdav-client:test";
+
+#[tokio::test]
+async fn test_proppatch_custom_props() -> Result<(), WebDavClientError> {
+    println!("======自定义属性测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./算法与分析.nol", true),
+        (WEBDAV_ENV_PATH_2, "./test.txt", true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let origin = PropName::new(TEST_NAMESPACE, "origin");
+    let mtime = PropName::new(TEST_NAMESPACE, "mtime");
+    let prop_names = [origin.clone(), mtime.clone()];
+
+    for (env_path, file_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let mut client = WebDavClient::new();
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let set_request = PropPatchRequest::new()
+            .with_set_prop(origin.clone(), "machine-<01>")
+            .with_set_prop(mtime.clone(), "1700000000");
+
+        let set_result =
+            client.proppatch(&key, file_path, &set_request).await;
+        let after_set =
+            client.get_custom_props(&key, file_path, &prop_names).await;
+
+        let remove_request = PropPatchRequest::new()
+            .with_remove_prop(origin.clone())
+            .with_remove_prop(mtime.clone());
+
+        let remove_result =
+            client.proppatch(&key, file_path, &remove_request).await;
+        let after_remove =
+            client.get_custom_props(&key, file_path, &prop_names).await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{env_path} -> {file_path} 设置结果: {set_result:?}");
+            println!("设置后读取: {after_set:?}");
+            println!("删除结果: {remove_result:?}");
+            println!("删除后读取: {after_remove:?}");
+        }
+
+        let is_ok = set_result.is_ok_and(|result| result.is_success())
+            && after_set.is_ok_and(|props| {
+                props.get(&origin).map(String::as_str)
+                    == Some("machine-<01>")
+                    && props.get(&mtime).map(String::as_str)
+                        == Some("1700000000")
+            })
+            && remove_result.is_ok_and(|result| result.is_success())
+            && after_remove.is_ok_and(|props| props.is_empty());
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "自定义属性测试失败: {} -> {}",
+            env_path, file_path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "自定义属性",
+    );
+
+    println!("======自定义属性测试结束======");
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::file_control::FileControl;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+#[tokio::test]
+async fn test_load_provider_profiles() -> Result<(), WebDavClientError> {
+    println!("======服务商配置测试开始======");
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let test_data =
+        vec![(WEBDAV_ENV_PATH_1, true), (WEBDAV_ENV_PATH_2, true)];
+
+    for (env_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        // 用户配置匹配测试账号，并禁止文件名包含 `#`
+        let config_path = std::env::temp_dir().join(format!(
+            "webdav-provider-profiles-{}.json",
+            std::process::id()
+        ));
+        let config = serde_json::json!({
+            "profiles": [{
+                "name": "custom",
+                "match_urls": [acc.url],
+                "forbidden_chars": ["#"],
+            }]
+        });
+        std::fs::write(&config_path, config.to_string())?;
+
+        let mut client = WebDavClient::new();
+        client.load_provider_profiles(&config_path)?;
+        std::fs::remove_file(&config_path)?;
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let profile = client.get_provider_profile(&key).await?;
+        let rejected = client.mkdir(&key, "./", "bad#name").await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!("{env_path} 匹配到的配置: {profile:?}");
+            println!("{env_path} 非法目录名: {rejected:?}");
+        }
+
+        let is_ok = profile.is_some_and(|p| p.name == "custom")
+            && rejected.is_err();
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(is_ok, *expected_ok, "服务商配置未生效: {}", env_path);
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "服务商配置",
+    );
+
+    println!("======服务商配置测试结束======");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::quota::Quota;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+#[cfg(feature = "show-test-detail")]
+use webdav_client::public_traits::friendly::FriendlyXml;
+
+#[tokio::test]
+async fn test_get_quota() -> Result<(), WebDavClientError> {
+    println!("======配额查询测试开始======");
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./", true),
+        (WEBDAV_ENV_PATH_1, "./不存在的目录/", false),
+        (WEBDAV_ENV_PATH_2, "./", true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = client.get_quota(&key, path).await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(quota) => {
+                    println!("✅ {env_path} -> {path}");
+                    println!("quota JSON: {}", quota.to_friendly_json()?);
+                }
+                Err(e) => println!("❌ {env_path} -> {path}: {e}"),
+            }
+        }
+
+        // 至少要拿到已用或可用空间之一
+        let is_ok = result.is_ok_and(|quota| {
+            quota.used_bytes.is_some()
+                || quota.available_bytes.is_some()
+                || quota.unlimited
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "配额查询测试失败: {} -> {}",
+            env_path, path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "配额查询",
+    );
+
+    println!("======配额查询测试结束======");
+    Ok(())
+}
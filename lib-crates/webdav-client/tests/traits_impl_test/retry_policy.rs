@@ -0,0 +1,81 @@
+use crate::assert_test_result;
+use std::time::{Duration, Instant};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::enums::client_enum::Depth;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::retry_policy::RetryPolicy;
+use webdav_client::client::traits::folder::Folder;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+
+/// 本机没有服务监听的端口，连接会被直接拒绝
+const REFUSED_URL: &str = "http://127.0.0.1:9/dav/";
+
+#[tokio::test]
+async fn test_retry_policy() -> Result<(), WebDavClientError> {
+    println!("======重试策略测试开始======");
+
+    let base_delay = Duration::from_millis(200);
+
+    // (策略, 至少耗时)：每次等待在 [d/2, d) 之间，d 依次翻倍
+    let test_data = vec![
+        (RetryPolicy::none(), Duration::ZERO, true),
+        (
+            RetryPolicy::new_default_config()
+                .with_max_attempts(3)
+                .with_delays(base_delay, Duration::from_secs(5)),
+            base_delay / 2 + base_delay,
+            true,
+        ),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (retry_policy, min_elapsed, expected_ok) in &test_data {
+        let mut client = WebDavClient::new();
+        client.set_retry_policy(retry_policy.clone());
+        let key = client.add_account(REFUSED_URL, "user", "password")?;
+
+        let started = Instant::now();
+        let result = client.get_folders(&key, "./", &Depth::One).await;
+        let elapsed = started.elapsed();
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "最多 {} 次，耗时 {elapsed:?}: {result:?}",
+                retry_policy.max_attempts
+            );
+        }
+
+        // 连接失败最终仍然返回错误，重试期间按退避等待
+        let is_ok = result.is_err() && elapsed >= *min_elapsed;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "重试策略测试失败: 最多 {} 次",
+            retry_policy.max_attempts
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "重试策略",
+    );
+
+    println!("======重试策略测试结束======");
+    Ok(())
+}
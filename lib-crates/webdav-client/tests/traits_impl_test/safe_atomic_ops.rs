@@ -2,7 +2,10 @@ use crate::{
     WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
 };
 use webdav_client::client::WebDavClient;
+use webdav_client::client::enums::client_enum::Depth;
 use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::auth::AuthStrategy;
+use webdav_client::client::traits::folder::Folder;
 use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
 
 #[tokio::test]
@@ -91,7 +94,8 @@ async fn test_remove_account() -> Result<(), WebDavClientError> {
             client.add_account(&acc.url, &acc.username, &acc.password)?;
 
         // 测试删除账号
-        let remove_result = client.remove_account(&acc.url, &acc.username).await;
+        let remove_result =
+            client.remove_account(&acc.url, &acc.username).await;
         let is_ok = remove_result.is_ok();
 
         if is_ok {
@@ -134,3 +138,78 @@ async fn test_remove_account() -> Result<(), WebDavClientError> {
     println!("======删除账号测试结束======");
     Ok(())
 }
+
+#[tokio::test]
+async fn test_add_account_with_auth() -> Result<(), WebDavClientError> {
+    println!("======指定认证方式新增账号测试开始======");
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    // (账号, 是否用正确的 Basic 凭据, 列目录是否应当成功)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, true, true),
+        (WEBDAV_ENV_PATH_2, true, true),
+        (WEBDAV_ENV_PATH_1, false, false),
+    ];
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, valid_auth, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let (username, auth) = if *valid_auth {
+            (
+                acc.username.clone(),
+                AuthStrategy::basic(&acc.username, &acc.password),
+            )
+        } else {
+            // 服务端不认这个令牌，应当 401
+            (
+                "quick-sync-bearer".to_string(),
+                AuthStrategy::bearer("quick-sync-invalid-token"),
+            )
+        };
+
+        let key =
+            client.add_account_with_auth(&acc.url, &username, auth)?;
+        let result = client.get_folders(&key, "./", &Depth::Zero).await;
+
+        let is_ok = result.is_ok();
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(_) => println!("✅ {env_path} -> {username}"),
+                Err(e) => println!("❌ {env_path} -> {username}: {e}"),
+            }
+        }
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "指定认证方式新增账号测试失败: {} -> {}",
+            env_path, username
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "指定认证方式新增账号",
+    );
+
+    println!("======指定认证方式新增账号测试结束======");
+    Ok(())
+}
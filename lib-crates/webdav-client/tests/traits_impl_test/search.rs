@@ -0,0 +1,71 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+use webdav_client::client::traits::search::{SearchFile, SearchQuery};
+
+#[tokio::test]
+async fn test_search() -> Result<(), WebDavClientError> {
+    println!("======搜索测试开始======");
+
+    // (账号, 搜索目录, 关键字, 是否应当有结果)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./", "算法", true),
+        (WEBDAV_ENV_PATH_2, "./", "test", true),
+        (WEBDAV_ENV_PATH_1, "./", "不存在的关键字-quick-sync", false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, path, keyword, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let hits = client
+            .search(&key, path, &SearchQuery::new(keyword), None)
+            .await?;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            for hit in &hits {
+                println!("🔍 {} -> {}", keyword, hit.full_path);
+            }
+        }
+
+        let is_ok = !hits.is_empty();
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "搜索测试失败: {} -> {}",
+            env_path, keyword
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "搜索",
+    );
+
+    println!("======搜索测试结束======");
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use crate::assert_test_result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::transfer_pool::{
+    TransferLimits, TransferPool,
+};
+use webdav_client::client::structs::webdav_child_client::WebDavChildClientKey;
+
+/// 同时 spawn `task_count` 个任务，返回同一时刻最多有几个在跑
+async fn max_running(
+    pool: &TransferPool,
+    keys: &[WebDavChildClientKey],
+    task_count: usize,
+) -> Result<usize, WebDavClientError> {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let mut handles = Vec::with_capacity(task_count);
+    for index in 0..task_count {
+        let running = Arc::clone(&running);
+        let peak = Arc::clone(&peak);
+        let key = &keys[index % keys.len()];
+
+        handles.push(
+            pool.spawn(key, async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+            .await?,
+        );
+    }
+
+    for handle in handles {
+        handle.await?;
+    }
+
+    Ok(peak.load(Ordering::SeqCst))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_transfer_pool_limits() -> Result<(), WebDavClientError> {
+    println!("======传输池并发上限测试开始======");
+
+    let same_host = [
+        WebDavChildClientKey::new("https://dav.example.com/dav/", "a")?,
+        WebDavChildClientKey::new("https://dav.example.com/dav/", "b")?,
+    ];
+    let other_hosts = [
+        WebDavChildClientKey::new("https://dav.example.com/dav/", "a")?,
+        WebDavChildClientKey::new("https://dav.example.org/dav/", "a")?,
+    ];
+
+    // (上限, 账号, 期望同时运行的最大任务数)
+    let test_data = vec![
+        // 三级都是 1，任务必须逐个执行
+        (TransferLimits::new(1, 1, 1), &same_host[..1], 1),
+        (TransferLimits::new(1, 8, 8), &same_host[..], 1),
+        // 同一主机的两个账号受主机上限约束
+        (TransferLimits::new(8, 1, 8), &same_host[..], 1),
+        // 每个账号 1 个，两个主机互不影响
+        (TransferLimits::new(8, 8, 1), &other_hosts[..], 2),
+        (TransferLimits::new(3, 8, 8), &same_host[..1], 3),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (limits, keys, expected_peak) in &test_data {
+        let pool = TransferPool::new(*limits);
+        let peak = max_running(&pool, keys, 8).await?;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{limits:?}: 同时运行 {peak} 个，期望 {expected_peak}"
+            );
+        }
+
+        let is_ok = peak == *expected_peak;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(is_ok, "传输池并发上限测试失败: {limits:?}");
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "传输池并发上限",
+    );
+
+    println!("======传输池并发上限测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_pool_try_acquire() -> Result<(), WebDavClientError>
+{
+    println!("======传输池额外许可测试开始======");
+
+    let key =
+        WebDavChildClientKey::new("https://dav.example.com/dav/", "a")?;
+    let other =
+        WebDavChildClientKey::new("https://dav.example.com/dav/", "b")?;
+
+    // (上限, 已占用许可的账号, 期望还能不等待拿到的许可数)
+    // 模拟一个文件已经占着一个许可，再为并发分片申请额外许可
+    let test_data = vec![
+        (TransferLimits::default(), &key, 7),
+        (TransferLimits::new(2, 8, 8), &key, 1),
+        (TransferLimits::new(8, 8, 1), &key, 0),
+        // 同一主机的其他账号占满了主机上限
+        (TransferLimits::new(8, 1, 8), &other, 0),
+        (TransferLimits::new(8, 4, 8), &other, 3),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    for (limits, held_by, expected_extra) in &test_data {
+        let pool = TransferPool::new(*limits);
+        let _held = pool.acquire(held_by).await?;
+
+        let try_all = || {
+            std::iter::from_fn(|| pool.try_acquire(&key))
+                .collect::<Vec<_>>()
+        };
+
+        let extra = try_all().len();
+        // 上一轮的许可已经归还，可以再次拿到
+        let again = try_all().len();
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{limits:?}: 额外拿到 {extra} 个，再次拿到 {again} 个，期望 {expected_extra}"
+            );
+        }
+
+        let is_ok = extra == *expected_extra && again == *expected_extra;
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert!(is_ok, "传输池额外许可测试失败: {limits:?}");
+    }
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        test_data.len(),
+        0,
+        "传输池额外许可",
+    );
+
+    println!("======传输池额外许可测试结束======");
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use futures_util::StreamExt;
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::traits::capabilities::Capabilities;
+use webdav_client::client::traits::file_control::FileControl;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+use webdav_client::client::traits::tree_walk::{TreeWalk, WalkConfig};
+
+#[tokio::test]
+async fn test_walk_tree() -> Result<(), WebDavClientError> {
+    println!("======目录树遍历测试开始======");
+
+    // (env, 路径, 并发数, 期望成功)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./上传测试", None, true),
+        (WEBDAV_ENV_PATH_1, "./上传测试", Some(1), true),
+        (WEBDAV_ENV_PATH_1, "./不存在的目录/", None, false),
+        (WEBDAV_ENV_PATH_2, "./上传测试", None, true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, path, max_concurrency, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let result = client.walk_tree(&key, path, *max_concurrency).await;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            match &result {
+                Ok(tree) => {
+                    println!(
+                        "✅ {env_path} -> {path}: {:?}，{} 个文件，共 {} 字节",
+                        tree.method,
+                        tree.file_count(),
+                        tree.total_size()
+                    );
+                    for entry in &tree.entries {
+                        println!("  {}", entry.relative_path);
+                    }
+                }
+                Err(e) => println!("❌ {env_path} -> {path}: {e}"),
+            }
+        }
+
+        // 目录自身不在结果里，每一项都在根目录下
+        let is_ok = result.is_ok_and(|tree| {
+            tree.file_count() > 0
+                && tree
+                    .entries
+                    .iter()
+                    .all(|entry| entry.url.starts_with(&tree.root_url))
+        });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "目录树遍历测试失败: {} -> {}",
+            env_path, path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "目录树遍历",
+    );
+
+    println!("======目录树遍历测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_walk_stream() -> Result<(), WebDavClientError> {
+    println!("======流式遍历测试开始======");
+
+    // (env, 路径, 遍历条件, 期望成功)
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, "./上传测试", WalkConfig::default(), true),
+        (
+            WEBDAV_ENV_PATH_1,
+            "./上传测试",
+            WalkConfig::default()
+                .with_max_depth(1)
+                .with_include_dirs(false),
+            true,
+        ),
+        (
+            WEBDAV_ENV_PATH_1,
+            "./上传测试",
+            WalkConfig::default().with_exclude("*"),
+            false,
+        ),
+        (
+            WEBDAV_ENV_PATH_1,
+            "./不存在的目录/",
+            WalkConfig::default(),
+            false,
+        ),
+        (WEBDAV_ENV_PATH_2, "./上传测试", WalkConfig::default(), true),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, path, walk_config, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let mut stream =
+            client.walk_stream(&key, path, walk_config.clone());
+
+        let mut resources = Vec::new();
+        let mut errors = Vec::new();
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(resource) => resources.push(resource),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path} -> {path} ({walk_config:?}): {} 项，{} 个错误",
+                resources.len(),
+                errors.len()
+            );
+            for resource in &resources {
+                println!("  {} {}", resource.is_dir, resource.full_path);
+            }
+            for e in &errors {
+                println!("  ❌ {e}");
+            }
+        }
+
+        // 返回的项要符合遍历条件
+        let is_ok = errors.is_empty()
+            && !resources.is_empty()
+            && resources.iter().all(|resource| {
+                walk_config.include_dirs || !resource.is_dir
+            });
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "流式遍历测试失败: {} -> {}",
+            env_path, path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "流式遍历",
+    );
+
+    println!("======流式遍历测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_walk_tree_empty_subdirs() -> Result<(), WebDavClientError> {
+    println!("======空子目录遍历测试开始======");
+
+    let test_data =
+        vec![(WEBDAV_ENV_PATH_1, true), (WEBDAV_ENV_PATH_2, true)];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        // 子目录都是空的，看不出服务端有没有展开 `Depth: infinity`
+        client.mkdir(&key, "./", "空子目录测试").await?;
+        client.mkdir(&key, "./空子目录测试", "a").await?;
+        client.mkdir(&key, "./空子目录测试", "b").await?;
+
+        let before = client.get_capabilities(&key).await?.depth_infinity;
+        let tree = client.walk_tree(&key, "./空子目录测试", None).await?;
+        let after = client.get_capabilities(&key).await?.depth_infinity;
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            println!(
+                "{env_path}: {:?}，{} 项，depth_infinity {before:?} -> {after:?}",
+                tree.method,
+                tree.entries.len()
+            );
+        }
+
+        // 只有两个空目录，并且不能因此记下服务端不支持 infinity
+        let is_ok = tree.entries.len() == 2
+            && tree.file_count() == 0
+            && (after == before || after == Some(true));
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "空子目录遍历测试失败: {env_path}"
+        );
+    }
+
+    let expected_ok_count = test_data.iter().filter(|(_, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "空子目录遍历",
+    );
+
+    println!("======空子目录遍历测试结束======");
+    Ok(())
+}
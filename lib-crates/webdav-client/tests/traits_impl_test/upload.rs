@@ -0,0 +1,184 @@
+use crate::{
+    WEBDAV_ENV_PATH_1, WEBDAV_ENV_PATH_2, assert_test_result, load_account,
+};
+use std::path::PathBuf;
+use webdav_client::client::WebDavClient;
+use webdav_client::client::error::WebDavClientError;
+use webdav_client::client::structs::progress::{
+    TransferEvent, progress_channel,
+};
+use webdav_client::client::structs::transfer_control::TransferControl;
+use webdav_client::client::traits::safe_atomic_ops::SafeAtomicOps;
+use webdav_client::client::traits::upload::{Upload, UploadConfig};
+
+/// 在系统临时目录下准备一个带子目录的本地测试目录
+fn prepare_local_dir() -> Result<PathBuf, WebDavClientError> {
+    let root = std::env::temp_dir().join("quick-sync-upload-test");
+    std::fs::create_dir_all(root.join("子目录"))?;
+    std::fs::write(root.join("a.txt"), "quick-sync upload test")?;
+    std::fs::write(root.join("子目录").join("b #1.txt"), "b")?;
+    Ok(root)
+}
+
+#[tokio::test]
+async fn test_upload_files() -> Result<(), WebDavClientError> {
+    println!("======上传文件测试开始======");
+
+    let local_dir = prepare_local_dir()?;
+    let local_dir = local_dir.to_string_lossy().to_string();
+
+    let test_data = vec![
+        (WEBDAV_ENV_PATH_1, local_dir.clone(), "./上传测试", true),
+        (WEBDAV_ENV_PATH_2, local_dir.clone(), "./上传测试", true),
+        (WEBDAV_ENV_PATH_1, "./不存在的文件.txt".to_string(), "./", false),
+    ];
+
+    let mut ok_count = 0;
+    let mut err_count = 0;
+
+    let mut client = WebDavClient::new();
+
+    for (env_path, local_path, remote_dir, expected_ok) in &test_data {
+        let acc = load_account(env_path);
+
+        let webdav_child_client_key =
+            client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+        let report = client
+            .upload_files(
+                &webdav_child_client_key,
+                vec![local_path.clone()],
+                remote_dir,
+                None,
+            )
+            .await?;
+
+        let is_ok = report.failed().is_empty();
+
+        #[cfg(feature = "show-test-detail")]
+        {
+            for file in &report.files {
+                println!(
+                    "{} {} -> {} ({}B)",
+                    if file.result.is_ok() { "✅" } else { "❌" },
+                    file.local_path,
+                    file.remote_url,
+                    file.bytes
+                );
+            }
+        }
+
+        if is_ok {
+            ok_count += 1;
+        } else {
+            err_count += 1;
+        }
+
+        assert_eq!(
+            is_ok, *expected_ok,
+            "上传测试失败: {} -> {}",
+            env_path, local_path
+        );
+    }
+
+    let expected_ok_count =
+        test_data.iter().filter(|(_, _, _, ok)| *ok).count();
+    let expected_err_count = test_data.len() - expected_ok_count;
+
+    assert_test_result(
+        ok_count,
+        err_count,
+        expected_ok_count,
+        expected_err_count,
+        "上传文件",
+    );
+
+    println!("======上传文件测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upload_progress() -> Result<(), WebDavClientError> {
+    println!("======上传进度测试开始======");
+
+    let local_dir = prepare_local_dir()?;
+    let local_dir = local_dir.to_string_lossy().to_string();
+
+    let mut client = WebDavClient::new();
+    let acc = load_account(WEBDAV_ENV_PATH_1);
+    let webdav_child_client_key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+    let (sender, mut receiver) = progress_channel();
+    let upload_config =
+        UploadConfig::new_default_config().with_progress(sender);
+
+    let report = client
+        .upload_files(
+            &webdav_child_client_key,
+            vec![local_dir],
+            "./上传测试",
+            Some(upload_config),
+        )
+        .await?;
+    assert!(report.failed().is_empty(), "上传进度测试上传失败");
+
+    let mut finished = 0;
+    let mut last_totals = None;
+    while let Ok(event) = receiver.try_recv() {
+        match event {
+            TransferEvent::FileFinished { .. } => finished += 1,
+            TransferEvent::Totals(totals) => last_totals = Some(totals),
+            _ => {}
+        }
+    }
+
+    let totals = last_totals.expect("没有收到汇总事件");
+    assert_eq!(finished, 2, "应该收到两个文件完成事件");
+    assert_eq!(totals.files_total, 2);
+    assert_eq!(totals.files_finished, 2);
+    assert_eq!(totals.bytes_transferred, totals.bytes_total);
+
+    println!("======上传进度测试结束======");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upload_cancelled() -> Result<(), WebDavClientError> {
+    println!("======取消上传测试开始======");
+
+    let local_dir = prepare_local_dir()?;
+    let local_dir = local_dir.to_string_lossy().to_string();
+
+    let mut client = WebDavClient::new();
+    let acc = load_account(WEBDAV_ENV_PATH_1);
+    let webdav_child_client_key =
+        client.add_account(&acc.url, &acc.username, &acc.password)?;
+
+    // 开始前就取消，所有条目都应该记为取消
+    let control = TransferControl::new();
+    control.cancel();
+    let upload_config =
+        UploadConfig::new_default_config().with_control(control);
+
+    let report = client
+        .upload_files(
+            &webdav_child_client_key,
+            vec![local_dir],
+            "./上传测试",
+            Some(upload_config),
+        )
+        .await?;
+
+    assert_eq!(report.success_count(), 0, "取消后不应该有成功的条目");
+    assert!(
+        report.files.iter().all(|f| matches!(
+            f.result,
+            Err(WebDavClientError::Cancelled)
+        )),
+        "取消后所有条目都应该是 Cancelled"
+    );
+
+    println!("======取消上传测试结束======");
+    Ok(())
+}